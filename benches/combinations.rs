@@ -110,6 +110,26 @@ fn comb_c14(c: &mut Criterion) {
     });
 }
 
+fn comb_a2(c: &mut Criterion) {
+    c.bench_function("comb a2", move |b| {
+        b.iter(|| {
+            for combo in (0..N2).array_combinations::<2>() {
+                black_box(combo);
+            }
+        })
+    });
+}
+
+fn comb_a4(c: &mut Criterion) {
+    c.bench_function("comb a4", move |b| {
+        b.iter(|| {
+            for combo in (0..N4).array_combinations::<4>() {
+                black_box(combo);
+            }
+        })
+    });
+}
+
 criterion_group!(
     benches,
     comb_for1,
@@ -121,5 +141,7 @@ criterion_group!(
     comb_c3,
     comb_c4,
     comb_c14,
+    comb_a2,
+    comb_a4,
 );
 criterion_main!(benches);
@@ -0,0 +1,226 @@
+#![cfg(feature = "use_alloc")]
+
+use std::fmt;
+
+use alloc::vec::Vec;
+
+use crate::lazy_buffer::LazyBuffer;
+use crate::size_hint;
+use super::multi_product::Odometer;
+
+/// An iterator adaptor that iterates over the cartesian product of `N` copies
+/// of an iterator, as arrays.
+///
+/// An iterator element type is `[I::Item; N]`.
+///
+/// See [`.cartesian_power()`](crate::Itertools::cartesian_power)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CartesianPower<I: Iterator, const N: usize> {
+    indices: [usize; N],
+    pool: LazyBuffer<I>,
+    first: bool,
+    done: bool,
+}
+
+/// An iterator adaptor that iterates over the cartesian product of `n` copies
+/// of an iterator, as `Vec`s.
+///
+/// An iterator element type is `Vec<I::Item>`.
+///
+/// See [`.cartesian_power_vec()`](crate::Itertools::cartesian_power_vec)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CartesianPowerVec<I: Iterator> {
+    indices: Vec<usize>,
+    pool: LazyBuffer<I>,
+    first: bool,
+    done: bool,
+}
+
+impl<I, const N: usize> Clone for CartesianPower<I, N>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(indices, pool, first, done);
+}
+
+impl<I> Clone for CartesianPowerVec<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(indices, pool, first, done);
+}
+
+impl<I, const N: usize> fmt::Debug for CartesianPower<I, N>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CartesianPower, indices, pool, first, done);
+}
+
+impl<I> fmt::Debug for CartesianPowerVec<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CartesianPowerVec, indices, pool, first, done);
+}
+
+/// Create a new `CartesianPower` from an iterator.
+pub fn cartesian_power<I, const N: usize>(iter: I) -> CartesianPower<I, N>
+    where I: Iterator,
+{
+    CartesianPower {
+        indices: [0; N],
+        pool: LazyBuffer::new(iter),
+        first: true,
+        done: false,
+    }
+}
+
+/// Create a new `CartesianPowerVec` from an iterator.
+pub fn cartesian_power_vec<I>(iter: I, n: usize) -> CartesianPowerVec<I>
+    where I: Iterator,
+{
+    CartesianPowerVec {
+        indices: alloc::vec![0; n],
+        pool: LazyBuffer::new(iter),
+        first: true,
+        done: false,
+    }
+}
+
+/// The indices of the current item into the buffered source, as the digits of
+/// an odometer. The rightmost index walks the source once, buffering it; the
+/// others only move after it is exhausted.
+struct Indices<'a, I: Iterator> {
+    indices: &'a mut [usize],
+    pool: &'a mut LazyBuffer<I>,
+}
+
+impl<'a, I: Iterator> Odometer for Indices<'a, I> {
+    fn digits(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn advance_digit(&mut self, i: usize) -> bool {
+        self.indices[i] += 1;
+        self.indices[i] < self.pool.len() || self.pool.get_next()
+    }
+
+    fn reset_digit(&mut self, i: usize) -> bool {
+        self.indices[i] = 0;
+        true
+    }
+}
+
+/// Moves `indices` to the next item of the product. On the first call, only
+/// checks that the product is not empty. Returns `false` once the last item is
+/// passed.
+fn iterate_indices<I: Iterator>(indices: &mut [usize], pool: &mut LazyBuffer<I>, first: bool) -> bool {
+    if first {
+        pool.prefill(1);
+        return indices.is_empty() || pool.len() > 0;
+    }
+
+    Indices { indices, pool }.advance()
+}
+
+/// Counts the items of the product left to yield if the source holds `n`
+/// elements in total, or `None` on overflow.
+///
+/// After the current item, each index `indices[i]` can still take
+/// `n - 1 - indices[i]` larger values, each followed by `n^(k - 1 - i)` choices
+/// for the indices to its right.
+fn remaining_for(n: usize, indices: &[usize], first: bool, done: bool) -> Option<usize> {
+    if done {
+        return Some(0);
+    }
+    if first {
+        return indices.iter().try_fold(1usize, |acc, _| acc.checked_mul(n));
+    }
+
+    let mut count: usize = 0;
+    let mut place = Some(1usize);
+    for &index in indices.iter().rev() {
+        let larger = n - 1 - index;
+        if larger > 0 {
+            count = count.checked_add(larger.checked_mul(place?)?)?;
+        }
+        place = place.and_then(|p| p.checked_mul(n));
+    }
+    Some(count)
+}
+
+impl<I, const N: usize> Iterator for CartesianPower<I, N>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || !iterate_indices(&mut self.indices, &mut self.pool, self.first) {
+            self.done = true;
+            return None;
+        }
+        self.first = false;
+
+        let pool = &self.pool;
+        Some(self.indices.map(|i| pool[i].clone()))
+    }
+
+    fn count(self) -> usize {
+        let n = self.pool.len() + self.pool.it.count();
+        match remaining_for(n, &self.indices, self.first, self.done) {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+        let remaining = |n| remaining_for(n, &self.indices, self.first, self.done);
+        (remaining(low).unwrap_or(usize::MAX), high.and_then(remaining))
+    }
+}
+
+impl<I> Iterator for CartesianPowerVec<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || !iterate_indices(&mut self.indices, &mut self.pool, self.first) {
+            self.done = true;
+            return None;
+        }
+        self.first = false;
+
+        Some(self.indices.iter().map(|&i| self.pool[i].clone()).collect())
+    }
+
+    fn count(self) -> usize {
+        let n = self.pool.len() + self.pool.it.count();
+        match remaining_for(n, &self.indices, self.first, self.done) {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+        let remaining = |n| remaining_for(n, &self.indices, self.first, self.done);
+        (remaining(low).unwrap_or(usize::MAX), high.and_then(remaining))
+    }
+}
+
+impl<I, const N: usize> ExactSizeIterator for CartesianPower<I, N>
+    where I: ExactSizeIterator,
+          I::Item: Clone,
+{}
+
+impl<I> ExactSizeIterator for CartesianPowerVec<I>
+    where I: ExactSizeIterator,
+          I::Item: Clone,
+{}
@@ -0,0 +1,300 @@
+#![cfg(feature = "use_alloc")]
+
+use std::fmt;
+
+use alloc::vec::Vec;
+
+use crate::lazy_buffer::LazyBuffer;
+use crate::size_hint;
+
+/// An iterator adaptor that iterates over the cartesian product of two
+/// iterators, one anti-diagonal at a time.
+///
+/// Iterator element type is `(I::Item, J::Item)`.
+///
+/// See [`.diagonal_product()`](crate::Itertools::diagonal_product) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct DiagonalProduct<I: Iterator, J: Iterator> {
+    a: LazyBuffer<I>,
+    b: LazyBuffer<J>,
+    // The sum of the positions of the current pair, and the position in `a`.
+    diagonal: usize,
+    i: usize,
+    // The last position in `a` on the current diagonal.
+    i_end: usize,
+    first: bool,
+    done: bool,
+    // Count of yielded elements.
+    pos: usize,
+}
+
+impl<I, J> Clone for DiagonalProduct<I, J>
+    where I: Clone + Iterator,
+          J: Clone + Iterator,
+          I::Item: Clone,
+          J::Item: Clone,
+{
+    clone_fields!(a, b, diagonal, i, i_end, first, done, pos);
+}
+
+impl<I, J> fmt::Debug for DiagonalProduct<I, J>
+    where I: Iterator + fmt::Debug,
+          J: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+          J::Item: fmt::Debug,
+{
+    debug_fmt_fields!(DiagonalProduct, a, b, diagonal, i, i_end, first, done, pos);
+}
+
+/// Create a new `DiagonalProduct` iterator.
+pub fn diagonal_product<I, J>(i: I, j: J) -> DiagonalProduct<I, J>
+    where I: Iterator,
+          J: Iterator,
+{
+    DiagonalProduct {
+        a: LazyBuffer::new(i),
+        b: LazyBuffer::new(j),
+        diagonal: 0,
+        i: 0,
+        i_end: 0,
+        first: true,
+        done: false,
+        pos: 0,
+    }
+}
+
+impl<I, J> DiagonalProduct<I, J>
+    where I: Iterator,
+          J: Iterator,
+{
+    /// Moves to the start of the next diagonal, buffering the elements it needs.
+    /// Returns `false` once there are no pairs left.
+    fn start_diagonal(&mut self) -> bool {
+        if self.first {
+            self.first = false;
+        } else {
+            self.diagonal += 1;
+        }
+        let s = self.diagonal;
+
+        // Each diagonal reaches one position further into both iterators.
+        self.a.prefill(s + 1);
+        self.b.prefill(s + 1);
+        if self.a.len() == 0 || self.b.len() == 0 {
+            return false;
+        }
+
+        // Positions past the end of an exhausted iterator are skipped.
+        self.i = s.saturating_sub(self.b.len() - 1);
+        self.i_end = s.min(self.a.len() - 1);
+        self.i <= self.i_end
+    }
+}
+
+impl<I, J> Iterator for DiagonalProduct<I, J>
+    where I: Iterator,
+          J: Iterator,
+          I::Item: Clone,
+          J::Item: Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.first || self.i > self.i_end {
+            // A diagonal without pairs means both iterators are exhausted.
+            if !self.start_diagonal() {
+                self.done = true;
+                return None;
+            }
+        }
+
+        let (i, j) = (self.i, self.diagonal - self.i);
+        self.i += 1;
+        self.pos = self.pos.saturating_add(1);
+        Some((self.a[i].clone(), self.b[j].clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let a_total = size_hint::add_scalar(self.a.it.size_hint(), self.a.len());
+        let b_total = size_hint::add_scalar(self.b.it.size_hint(), self.b.len());
+        let total = size_hint::mul(a_total, b_total);
+
+        if self.pos < usize::MAX {
+            // Subtract count of elements already yielded from total.
+            size_hint::sub_scalar(total, self.pos)
+        } else {
+            // Fallback: self.pos is saturated and no longer reliable.
+            (0, total.1)
+        }
+    }
+}
+
+/// An iterator adaptor that iterates over the cartesian product of multiple
+/// iterators of type `I`, one anti-diagonal at a time.
+///
+/// An iterator element type is `Vec<I::Item>`.
+///
+/// See [`.multi_diagonal_product()`](crate::Itertools::multi_diagonal_product)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiDiagonalProduct<I: Iterator> {
+    pools: Vec<LazyBuffer<I>>,
+    // The current tuple of positions, and their sum.
+    indices: Vec<usize>,
+    diagonal: usize,
+    first: bool,
+    done: bool,
+    // Count of yielded elements.
+    pos: usize,
+}
+
+impl<I> Clone for MultiDiagonalProduct<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(pools, indices, diagonal, first, done, pos);
+}
+
+impl<I> fmt::Debug for MultiDiagonalProduct<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(MultiDiagonalProduct, pools, indices, diagonal, first, done, pos);
+}
+
+/// Create a new `MultiDiagonalProduct` iterator over an arbitrary number of
+/// iterators of the same type.
+pub fn multi_diagonal_product<H>(iters: H) -> MultiDiagonalProduct<<H::Item as IntoIterator>::IntoIter>
+    where H: Iterator,
+          H::Item: IntoIterator,
+{
+    let pools: Vec<_> = iters.map(|i| LazyBuffer::new(i.into_iter())).collect();
+    MultiDiagonalProduct {
+        indices: alloc::vec![0; pools.len()],
+        pools,
+        diagonal: 0,
+        first: true,
+        done: false,
+        pos: 0,
+    }
+}
+
+impl<I> MultiDiagonalProduct<I>
+    where I: Iterator,
+{
+    /// Returns the largest position usable in the iterator at `d` on the current
+    /// diagonal.
+    fn cap(&self, d: usize) -> usize {
+        self.diagonal.min(self.pools[d].len() - 1)
+    }
+
+    /// Sets the positions from `from` on to the lexicographically smallest ones
+    /// that sum up to `rest`.
+    fn fill(&mut self, from: usize, mut rest: usize) {
+        for d in from..self.indices.len() {
+            // Leave as much as the positions after `d` can take.
+            let after: usize = (d + 1..self.indices.len())
+                .fold(0, |acc, e| acc.saturating_add(self.cap(e)));
+            self.indices[d] = rest.saturating_sub(after);
+            rest -= self.indices[d];
+        }
+    }
+
+    /// Moves to the start of the next diagonal, buffering the elements it needs.
+    /// Returns `false` once there are no tuples left.
+    fn start_diagonal(&mut self) -> bool {
+        if self.first {
+            self.first = false;
+        } else {
+            self.diagonal += 1;
+        }
+        let s = self.diagonal;
+
+        // Each diagonal reaches one position further into every iterator.
+        for pool in &mut self.pools {
+            pool.prefill(s + 1);
+            if pool.len() == 0 {
+                return false;
+            }
+        }
+
+        // The diagonal is out of reach once every iterator is exhausted.
+        let reach = (0..self.pools.len()).fold(0usize, |acc, d| acc.saturating_add(self.cap(d)));
+        if self.pools.is_empty() || reach < s {
+            return false;
+        }
+
+        self.fill(0, s);
+        true
+    }
+
+    /// Moves to the next tuple of positions on the current diagonal, in
+    /// lexicographic order. Returns `false` at the end of the diagonal.
+    fn advance(&mut self) -> bool {
+        let n = self.indices.len();
+        let mut rest = self.diagonal;
+        let mut prefix_sums = Vec::with_capacity(n);
+        for &index in &self.indices {
+            prefix_sums.push(rest);
+            rest -= index;
+        }
+
+        // Scan from the end, looking for a position that can take a larger value
+        for d in (0..n.saturating_sub(1)).rev() {
+            let rest = prefix_sums[d];
+            if self.indices[d] < self.cap(d) && self.indices[d] < rest {
+                self.indices[d] += 1;
+                self.fill(d + 1, rest - self.indices[d]);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<I> Iterator for MultiDiagonalProduct<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if (self.first || !self.advance()) && !self.start_diagonal() {
+            self.done = true;
+            return None;
+        }
+
+        self.pos = self.pos.saturating_add(1);
+        Some(self.indices.iter().zip(&self.pools).map(|(&i, pool)| pool[i].clone()).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done || self.pools.is_empty() {
+            return (0, Some(0));
+        }
+
+        let total = self.pools.iter().fold((1, Some(1)), |acc, pool| {
+            size_hint::mul(acc, size_hint::add_scalar(pool.it.size_hint(), pool.len()))
+        });
+
+        if self.pos < usize::MAX {
+            // Subtract count of elements already yielded from total.
+            size_hint::sub_scalar(total, self.pos)
+        } else {
+            // Fallback: self.pos is saturated and no longer reliable.
+            (0, total.1)
+        }
+    }
+}
@@ -4,10 +4,16 @@
 //! option. This file may not be copied, modified, or distributed
 //! except according to those terms.
 
+mod cartesian_power;
 mod coalesce;
+mod diagonal_product;
 mod map;
 mod multi_product;
+#[cfg(feature = "use_alloc")]
+pub use self::cartesian_power::*;
 pub use self::coalesce::*;
+#[cfg(feature = "use_alloc")]
+pub use self::diagonal_product::*;
 pub use self::map::{map_into, map_ok, MapInto, MapOk};
 #[allow(deprecated)]
 pub use self::map::MapResults;
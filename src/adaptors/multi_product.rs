@@ -14,9 +14,14 @@ use alloc::vec::Vec;
 /// See [`.multi_cartesian_product()`](crate::Itertools::multi_cartesian_product)
 /// for more information.
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct MultiProduct<I>(Vec<MultiProductIter<I>>)
+pub struct MultiProduct<I>
     where I: Iterator + Clone,
-          I::Item: Clone;
+          I::Item: Clone
+{
+    iters: Vec<MultiProductIter<I>>,
+    // Number of items left to yield, if split off by `split_at`.
+    limit: Option<usize>,
+}
 
 /// Create a new cartesian product iterator over an arbitrary number
 /// of iterators of the same type.
@@ -28,7 +33,10 @@ pub fn multi_cartesian_product<H>(iters: H) -> MultiProduct<<H::Item as IntoIter
           <H::Item as IntoIterator>::IntoIter: Clone,
           <H::Item as IntoIterator>::Item: Clone
 {
-    MultiProduct(iters.map(|i| MultiProductIter::new(i.into_iter())).collect())
+    MultiProduct {
+        iters: iters.map(|i| MultiProductIter::new(i.into_iter())).collect(),
+        limit: None,
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -42,65 +50,73 @@ struct MultiProductIter<I>
     iter_orig: I,
 }
 
-/// Holds the current state during an iteration of a MultiProduct.
-#[derive(Debug)]
-enum MultiProductIterState {
-    StartOfIter,
-    MidIter { on_first_iter: bool },
+/// A row of digits, each running through its own sequence of values, that
+/// count like an odometer: the rightmost digit moves fastest.
+///
+/// This drives both `MultiProduct`, whose digits are iterators, and
+/// `CartesianPower`, whose digits are indices into a shared buffer.
+pub(crate) trait Odometer {
+    /// Returns the number of digits.
+    fn digits(&self) -> usize;
+
+    /// Moves digit `i` to its next value. Returns `false` if it had none left.
+    fn advance_digit(&mut self, i: usize) -> bool;
+
+    /// Moves digit `i` back to its first value. Returns `false` if it has none.
+    fn reset_digit(&mut self, i: usize) -> bool;
+
+    /// Moves to the next reading: advances the rightmost digit that has a next
+    /// value, and resets all the digits to its right.
+    ///
+    /// Returns `false` once no digit can advance, leaving them as they are.
+    fn advance(&mut self) -> bool {
+        let len = self.digits();
+        match (0..len).rev().find(|&i| self.advance_digit(i)) {
+            Some(i) => (i + 1..len).all(|j| self.reset_digit(j)),
+            None => false,
+        }
+    }
+}
+
+impl<I> Odometer for [MultiProductIter<I>]
+    where I: Iterator + Clone,
+          I::Item: Clone
+{
+    fn digits(&self) -> usize {
+        self.len()
+    }
+
+    fn advance_digit(&mut self, i: usize) -> bool {
+        self[i].iterate();
+        self[i].in_progress()
+    }
+
+    fn reset_digit(&mut self, i: usize) -> bool {
+        self[i].reset();
+        self.advance_digit(i)
+    }
 }
 
 impl<I> MultiProduct<I>
     where I: Iterator + Clone,
           I::Item: Clone
 {
-    /// Iterates the rightmost iterator, then recursively iterates iterators
-    /// to the left if necessary.
+    /// Iterates the rightmost iterator, carrying into the iterators to the
+    /// left when it finishes. Before the first item, starts all of them.
     ///
     /// Returns true if the iteration succeeded, else false.
-    fn iterate_last(
-        multi_iters: &mut [MultiProductIter<I>],
-        mut state: MultiProductIterState
-    ) -> bool {
-        use self::MultiProductIterState::*;
-
-        if let Some((last, rest)) = multi_iters.split_last_mut() {
-            let on_first_iter = match state {
-                StartOfIter => {
-                    let on_first_iter = !last.in_progress();
-                    state = MidIter { on_first_iter };
-                    on_first_iter
-                },
-                MidIter { on_first_iter } => on_first_iter
-            };
-
-            if !on_first_iter {
-                last.iterate();
-            }
-
-            if last.in_progress() {
-                true
-            } else if MultiProduct::iterate_last(rest, state) {
-                last.reset();
-                last.iterate();
-                // If iterator is None twice consecutively, then iterator is
-                // empty; whole product is empty.
-                last.in_progress()
-            } else {
-                false
-            }
-        } else {
-            // Reached end of iterator list. On initialisation, return true.
-            // At end of iteration (final iterator finishes), finish.
-            match state {
-                StartOfIter => false,
-                MidIter { on_first_iter } => on_first_iter
-            }
+    fn iterate_last(multi_iters: &mut [MultiProductIter<I>]) -> bool {
+        match multi_iters.last() {
+            None => false,
+            Some(last) if last.in_progress() => multi_iters.advance(),
+            // An empty iterator makes the whole product empty.
+            Some(_) => (0..multi_iters.len()).all(|i| multi_iters.reset_digit(i)),
         }
     }
 
     /// Returns the unwrapped value of the next iteration.
     fn curr_iterator(&self) -> Vec<I::Item> {
-        self.0.iter().map(|multi_iter| {
+        self.iters.iter().map(|multi_iter| {
             multi_iter.cur.clone().unwrap()
         }).collect()
     }
@@ -108,12 +124,114 @@ impl<I> MultiProduct<I>
     /// Returns true if iteration has started and has not yet finished; false
     /// otherwise.
     fn in_progress(&self) -> bool {
-        if let Some(last) = self.0.last() {
+        if let Some(last) = self.iters.last() {
             last.in_progress()
         } else {
             false
         }
     }
+
+    /// Moves past the next `steps` items without yielding them. Returns `false`
+    /// if there are not that many left.
+    ///
+    /// The position of each iterator is a digit of the rank of the current item,
+    /// so this adds `steps` to it digit by digit and then moves each iterator
+    /// straight to its new position with `nth`.
+    fn skip_ahead(&mut self, steps: usize) -> bool {
+        if steps == 0 {
+            return true;
+        }
+
+        let sizes: Vec<usize> = self.iters.iter()
+            .map(|multi_iter| multi_iter.iter_orig.clone().count())
+            .collect();
+        if self.iters.is_empty() || sizes.contains(&0) {
+            return false;
+        }
+
+        // Before the first item, moving onto it takes one step.
+        let (mut digits, mut carry): (Vec<usize>, usize) = if self.in_progress() {
+            let digits = self.iters.iter().zip(&sizes)
+                .map(|(multi_iter, &size)| size - 1 - multi_iter.iter.clone().count())
+                .collect();
+            (digits, steps)
+        } else {
+            (alloc::vec![0; sizes.len()], steps - 1)
+        };
+
+        for (digit, &radix) in digits.iter_mut().zip(&sizes).rev() {
+            let (quotient, rem) = (carry / radix, carry % radix);
+            if *digit >= radix - rem {
+                *digit -= radix - rem;
+                carry = quotient + 1;
+            } else {
+                *digit += rem;
+                carry = quotient;
+            }
+        }
+        if carry > 0 {
+            return false;
+        }
+
+        for (multi_iter, &digit) in self.iters.iter_mut().zip(&digits) {
+            multi_iter.reset();
+            multi_iter.cur = multi_iter.iter.nth(digit);
+        }
+        true
+    }
+
+    /// Splits the remaining items into two iterators: one over the first `index`
+    /// of them, and one over the rest.
+    ///
+    /// Both halves start from their position by moving each iterator with `nth`,
+    /// rather than by stepping through the items before it.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (front, back) = (0..2).map(|_| 0..3).multi_cartesian_product().split_at(4);
+    /// itertools::assert_equal(front, vec![vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 0]]);
+    /// itertools::assert_equal(back, vec![
+    ///     vec![1, 1],
+    ///     vec![1, 2],
+    ///     vec![2, 0],
+    ///     vec![2, 1],
+    ///     vec![2, 2],
+    /// ]);
+    /// ```
+    pub fn split_at(mut self, index: usize) -> (Self, Self) {
+        let mut back = self.clone();
+        let left = self.limit.map_or(index, |limit| limit.min(index));
+        back.limit = back.limit.map(|limit| limit - left);
+        if !back.skip_ahead(index) {
+            back.limit = Some(0);
+        }
+
+        self.limit = Some(left);
+        (self, back)
+    }
+
+    /// Splits the remaining items into `n` iterators over consecutive ranges of
+    /// them, whose lengths differ by at most one.
+    ///
+    /// This is convenient to divide the work between `n` threads.
+    ///
+    /// **Panics** if `n` is zero, or if the number of items left is greater
+    /// than `usize::MAX`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let product = || (0..3).map(|_| 0..2).multi_cartesian_product();
+    /// let parts = product().split_into(3);
+    /// let lens: Vec<usize> = parts.iter().map(|part| part.clone().count()).collect();
+    /// assert_eq!(lens, vec![3, 3, 2]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), product());
+    /// ```
+    pub fn split_into(self, n: usize) -> Vec<Self> {
+        let len = self.clone().count();
+        crate::split::split_into(self, len, n, Self::split_at)
+    }
 }
 
 impl<I> MultiProductIter<I>
@@ -152,10 +270,14 @@ impl<I> Iterator for MultiProduct<I>
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if MultiProduct::iterate_last(
-            &mut self.0,
-            MultiProductIterState::StartOfIter
-        ) {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return None;
+            }
+            self.limit = Some(limit - 1);
+        }
+
+        if MultiProduct::iterate_last(&mut self.iters) {
             Some(self.curr_iterator())
         } else {
             None
@@ -163,52 +285,64 @@ impl<I> Iterator for MultiProduct<I>
     }
 
     fn count(self) -> usize {
-        if self.0.is_empty() {
+        if self.iters.is_empty() || self.limit == Some(0) {
             return 0;
         }
 
+        let limit = self.limit.unwrap_or(usize::MAX);
         if !self.in_progress() {
-            return self.0.into_iter().fold(1, |acc, multi_iter| {
+            return self.iters.into_iter().fold(1, |acc, multi_iter| {
                 acc * multi_iter.iter.count()
-            });
+            }).min(limit);
         }
 
-        self.0.into_iter().fold(
+        self.iters.into_iter().fold(
             0,
             |acc, MultiProductIter { iter, iter_orig, cur: _ }| {
                 let total_count = iter_orig.count();
                 let cur_count = iter.count();
                 acc * total_count + cur_count
             }
-        )
+        ).min(limit)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         // Not ExactSizeIterator because size may be larger than usize
-        if self.0.is_empty() {
+        if self.iters.is_empty() {
             return (0, Some(0));
         }
 
-        if !self.in_progress() {
-            return self.0.iter().fold((1, Some(1)), |acc, multi_iter| {
+        let hint = if !self.in_progress() {
+            self.iters.iter().fold((1, Some(1)), |acc, multi_iter| {
                 size_hint::mul(acc, multi_iter.iter.size_hint())
-            });
-        }
+            })
+        } else {
+            self.iters.iter().fold(
+                (0, Some(0)),
+                |acc, &MultiProductIter { ref iter, ref iter_orig, cur: _ }| {
+                    let cur_size = iter.size_hint();
+                    let total_size = iter_orig.size_hint();
+                    size_hint::add(size_hint::mul(acc, total_size), cur_size)
+                }
+            )
+        };
 
-        self.0.iter().fold(
-            (0, Some(0)),
-            |acc, &MultiProductIter { ref iter, ref iter_orig, cur: _ }| {
-                let cur_size = iter.size_hint();
-                let total_size = iter_orig.size_hint();
-                size_hint::add(size_hint::mul(acc, total_size), cur_size)
-            }
-        )
+        match self.limit {
+            Some(limit) => size_hint::min(hint, (limit, Some(limit))),
+            None => hint,
+        }
     }
 
     fn last(self) -> Option<Self::Item> {
-        let iter_count = self.0.len();
+        if self.limit.is_some() || self.in_progress() {
+            // The last item of a split off range is not the last of each iterator,
+            // and once started, the iterators to the left may already be at theirs.
+            return self.fold(None, |_, item| Some(item));
+        }
+
+        let iter_count = self.iters.len();
 
-        let lasts: Self::Item = self.0.into_iter()
+        let lasts: Self::Item = self.iters.into_iter()
             .map(|multi_iter| multi_iter.iter.last())
             .while_some()
             .collect();
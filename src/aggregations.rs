@@ -0,0 +1,249 @@
+//! Named aggregations for
+//! [`GroupingMap::aggregate_many`](crate::structs::GroupingMap::aggregate_many).
+//!
+//! Each of these computes one result per group, and tuples of them compute
+//! a tuple of results in a single pass:
+//!
+//! ```
+//! use itertools::Itertools;
+//! use itertools::aggregations::{Count, Max, Mean, Sum};
+//!
+//! let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+//!     .into_grouping_map_by(|&n| n % 3)
+//!     .aggregate_many((Count, Sum, Max, Mean));
+//!
+//! let (count, sum, max, mean) = lookup[&0];
+//! assert_eq!((count, sum, max), (3, 3 + 9 + 12, 12));
+//! assert_eq!(mean.mean(), 8.0);
+//! ```
+
+use std::ops::{Add, Mul};
+
+/// An aggregation of the elements of each group, computed one element at a
+/// time.
+///
+/// The elements are passed by reference so that several aggregations can see
+/// each of them; the ones that keep elements clone them, into the buffers of
+/// the values they replace with [`Clone::clone_from`].
+pub trait Aggregation<K, V> {
+    /// The result for each group.
+    type Output;
+
+    /// Starts the result of a group with its first element.
+    fn start(&mut self, key: &K, val: &V) -> Self::Output;
+
+    /// Updates the result of a group with one more of its elements.
+    fn update(&mut self, acc: Self::Output, key: &K, val: &V) -> Self::Output;
+}
+
+/// Counts the elements of each group.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Count;
+
+impl<K, V> Aggregation<K, V> for Count {
+    type Output = usize;
+
+    fn start(&mut self, _: &K, _: &V) -> usize {
+        1
+    }
+
+    fn update(&mut self, acc: usize, _: &K, _: &V) -> usize {
+        acc + 1
+    }
+}
+
+/// Keeps the first element of each group.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct First;
+
+impl<K, V: Clone> Aggregation<K, V> for First {
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, acc: V, _: &K, _: &V) -> V {
+        acc
+    }
+}
+
+/// Keeps the last element of each group.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Last;
+
+impl<K, V: Clone> Aggregation<K, V> for Last {
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, mut acc: V, _: &K, val: &V) -> V {
+        acc.clone_from(val);
+        acc
+    }
+}
+
+/// Sums the elements of each group.
+///
+/// Each element is cloned to be added.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sum;
+
+impl<K, V> Aggregation<K, V> for Sum
+    where V: Clone + Add<V, Output = V>,
+{
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, acc: V, _: &K, val: &V) -> V {
+        acc + val.clone()
+    }
+}
+
+/// Multiplies the elements of each group.
+///
+/// Each element is cloned to be multiplied.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Product;
+
+impl<K, V> Aggregation<K, V> for Product
+    where V: Clone + Mul<V, Output = V>,
+{
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, acc: V, _: &K, val: &V) -> V {
+        acc * val.clone()
+    }
+}
+
+/// Finds the minimum of each group, the first one if several are equal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Min;
+
+impl<K, V: Clone + Ord> Aggregation<K, V> for Min {
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, mut acc: V, _: &K, val: &V) -> V {
+        if *val < acc {
+            acc.clone_from(val);
+        }
+        acc
+    }
+}
+
+/// Finds the maximum of each group, the last one if several are equal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Max;
+
+impl<K, V: Clone + Ord> Aggregation<K, V> for Max {
+    type Output = V;
+
+    fn start(&mut self, _: &K, val: &V) -> V {
+        val.clone()
+    }
+
+    fn update(&mut self, mut acc: V, _: &K, val: &V) -> V {
+        if *val >= acc {
+            acc.clone_from(val);
+        }
+        acc
+    }
+}
+
+/// Averages the elements of each group, as a [`RunningMean`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mean;
+
+impl<K, V> Aggregation<K, V> for Mean
+    where V: Clone + Into<f64>,
+{
+    type Output = RunningMean;
+
+    fn start(&mut self, _: &K, val: &V) -> RunningMean {
+        RunningMean::new(val.clone().into())
+    }
+
+    fn update(&mut self, acc: RunningMean, _: &K, val: &V) -> RunningMean {
+        acc.push(val.clone().into())
+    }
+}
+
+/// The sum and count of the elements of a group, from which their mean
+/// follows.
+///
+/// See [`GroupingMap::mean`](crate::structs::GroupingMap::mean).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunningMean {
+    sum: f64,
+    count: usize,
+}
+
+impl RunningMean {
+    pub(crate) fn new(x: f64) -> Self {
+        RunningMean { sum: x, count: 1 }
+    }
+
+    pub(crate) fn push(self, x: f64) -> Self {
+        RunningMean { sum: self.sum + x, count: self.count + 1 }
+    }
+
+    /// Returns the mean of the elements.
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+
+    /// Returns the sum of the elements.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the number of elements.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+macro_rules! impl_aggregation_tuple {
+    ($($A:ident $acc:ident),+) => {
+        impl<K, V, $($A),+> Aggregation<K, V> for ($($A,)+)
+            where $($A: Aggregation<K, V>),+
+        {
+            type Output = ($($A::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn start(&mut self, key: &K, val: &V) -> Self::Output {
+                let ($(ref mut $A,)+) = *self;
+                ($($A.start(key, val),)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn update(&mut self, acc: Self::Output, key: &K, val: &V) -> Self::Output {
+                let ($(ref mut $A,)+) = *self;
+                let ($($acc,)+) = acc;
+                ($($A.update($acc, key, val),)+)
+            }
+        }
+    };
+}
+
+impl_aggregation_tuple!(A a);
+impl_aggregation_tuple!(A a, B b);
+impl_aggregation_tuple!(A a, B b, C c);
+impl_aggregation_tuple!(A a, B b, C c, D d);
+impl_aggregation_tuple!(A a, B b, C c, D d, E e);
+impl_aggregation_tuple!(A a, B b, C c, D d, E e, F f);
+impl_aggregation_tuple!(A a, B b, C c, D d, E e, F f, G g);
+impl_aggregation_tuple!(A a, B b, C c, D d, E e, F f, G g, H h);
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use super::combinations::{advance_indices, increment_indices};
+use super::lazy_buffer::LazyBuffer;
+
+/// An iterator to iterate through all the `K`-length combinations in an iterator,
+/// as arrays.
+///
+/// See [`.array_combinations()`](crate::Itertools::array_combinations) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ArrayCombinations<I: Iterator, const K: usize> {
+    indices: [usize; K],
+    pool: LazyBuffer<I>,
+    first: bool,
+}
+
+impl<I, const K: usize> Clone for ArrayCombinations<I, K>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(indices, pool, first);
+}
+
+impl<I, const K: usize> fmt::Debug for ArrayCombinations<I, K>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(ArrayCombinations, indices, pool, first);
+}
+
+/// Create a new `ArrayCombinations` from an iterator.
+pub fn array_combinations<I, const K: usize>(iter: I) -> ArrayCombinations<I, K>
+    where I: Iterator
+{
+    let mut pool = LazyBuffer::new(iter);
+    pool.prefill(K);
+
+    let mut indices = [0; K];
+    for (i, index) in indices.iter_mut().enumerate() {
+        *index = i;
+    }
+
+    ArrayCombinations {
+        indices,
+        pool,
+        first: true,
+    }
+}
+
+impl<I, const K: usize> ArrayCombinations<I, K>
+    where I: Iterator,
+{
+    /// Returns the (current) length of the pool from which combination elements are
+    /// selected. This value can change between invocations of [`next`].
+    ///
+    /// [`next`]: #method.next
+    #[inline]
+    pub fn n(&self) -> usize { self.pool.len() }
+
+    /// Clones the current combination out of the pool.
+    fn current(&self) -> [I::Item; K]
+        where I::Item: Clone
+    {
+        self.indices.map(|i| self.pool[i].clone())
+    }
+}
+
+impl<I, const K: usize> Iterator for ArrayCombinations<I, K>
+    where I: Iterator,
+          I::Item: Clone
+{
+    type Item = [I::Item; K];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            if K > self.n() {
+                return None;
+            }
+            self.first = false;
+        } else if !increment_indices(&mut self.indices, &mut self.pool) {
+            return None;
+        }
+
+        Some(self.current())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let steps = if self.first {
+            if K > self.n() {
+                return None;
+            }
+            self.first = false;
+            n
+        } else {
+            match n.checked_add(1) {
+                Some(steps) => steps,
+                None if increment_indices(&mut self.indices, &mut self.pool) => n,
+                None => return None,
+            }
+        };
+
+        if steps == 0 || advance_indices(&mut self.indices, &mut self.pool, steps) {
+            Some(self.current())
+        } else {
+            None
+        }
+    }
+}
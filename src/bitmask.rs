@@ -0,0 +1,295 @@
+use std::iter::FusedIterator;
+
+/// An iterator over the positions of the set bits of a mask.
+///
+/// See [`.iter_bits()`](IterBits::iter_bits) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Bits {
+    mask: u64,
+}
+
+/// An extension trait for integers used as bit sets.
+pub trait IterBits {
+    /// Return an iterator over the positions of the set bits of `self`, from
+    /// the lowest to the highest.
+    ///
+    /// The iterator is double ended, and knows its exact length.
+    ///
+    /// ```
+    /// use itertools::IterBits;
+    ///
+    /// itertools::assert_equal(0b1011_0010u8.iter_bits(), vec![1, 4, 5, 7]);
+    /// itertools::assert_equal(0b1011_0010u8.iter_bits().rev(), vec![7, 5, 4, 1]);
+    /// assert_eq!(u64::MAX.iter_bits().len(), 64);
+    /// ```
+    fn iter_bits(self) -> Bits;
+}
+
+macro_rules! impl_iter_bits {
+    ($($t:ty)*) => {
+        $(
+            impl IterBits for $t {
+                fn iter_bits(self) -> Bits {
+                    Bits { mask: self as u64 }
+                }
+            }
+        )*
+    };
+}
+
+impl_iter_bits!(u8 u16 u32 u64 usize);
+
+impl Iterator for Bits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let bit = self.mask.trailing_zeros();
+        // Clear the lowest set bit
+        self.mask &= self.mask - 1;
+        Some(bit as usize)
+    }
+
+    fn count(self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Bits {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let bit = 63 - self.mask.leading_zeros();
+        self.mask ^= 1 << bit;
+        Some(bit as usize)
+    }
+}
+
+impl ExactSizeIterator for Bits {}
+
+impl FusedIterator for Bits {}
+
+/// An iterator over the submasks of a mask.
+///
+/// See [`subsets_of_mask`] for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SubsetsOfMask {
+    mask: u64,
+    // The next submasks to yield from the front and from the back.
+    front: u64,
+    back: u64,
+    // Submasks left to yield, up to `2^64` for the full mask.
+    remaining: u128,
+}
+
+/// Return an iterator over all the submasks of `mask`, in increasing order.
+///
+/// Iterator element type is `u64`. Each submask `s` satisfies `s & mask == s`,
+/// and there are `2^k` of them where `k` is the number of set bits of `mask`.
+/// For `mask = (1 << n) - 1`, this is a cheap counterpart to
+/// [`.powerset()`](crate::Itertools::powerset) over `n` items that needs no
+/// allocation, although the subsets come in a different order.
+///
+/// The iterator is double ended, and knows its exact length, except for the
+/// `2^64` submasks of `u64::MAX` which do not fit in a `usize`.
+///
+/// ```
+/// use itertools::subsets_of_mask;
+///
+/// itertools::assert_equal(subsets_of_mask(0b1010), vec![0b0000, 0b0010, 0b1000, 0b1010]);
+/// itertools::assert_equal(subsets_of_mask(0b101).rev(), vec![0b101, 0b100, 0b001, 0b000]);
+/// assert_eq!(subsets_of_mask(0b1111).len(), 16);
+/// ```
+pub fn subsets_of_mask(mask: u64) -> SubsetsOfMask {
+    SubsetsOfMask {
+        mask,
+        front: 0,
+        back: mask,
+        remaining: 1 << mask.count_ones(),
+    }
+}
+
+impl Iterator for SubsetsOfMask {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let subset = self.front;
+        // Add one, carrying through the bits outside of the mask
+        self.front = (subset | !self.mask).wrapping_add(1) & self.mask;
+        Some(subset)
+    }
+
+    fn count(self) -> usize {
+        if self.remaining > usize::MAX as u128 {
+            panic!("Iterator count greater than usize::MAX");
+        }
+        self.remaining as usize
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining > usize::MAX as u128 {
+            return (usize::MAX, None);
+        }
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for SubsetsOfMask {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let subset = self.back;
+        // Subtract one, borrowing through the bits outside of the mask
+        self.back = subset.wrapping_sub(1) & self.mask;
+        Some(subset)
+    }
+}
+
+impl ExactSizeIterator for SubsetsOfMask {}
+
+impl FusedIterator for SubsetsOfMask {}
+
+/// An iterator over the masks of `n` bits with `k` of them set.
+///
+/// See [`masks_with_popcount`] for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct MasksWithPopcount {
+    // The mask of all `n` bits.
+    full: u64,
+    // The next masks to yield from the front and from the back.
+    front: u64,
+    back: u64,
+    remaining: u64,
+}
+
+/// Return an iterator over the masks of the lowest `n` bits with exactly `k`
+/// of them set, in increasing order.
+///
+/// Iterator element type is `u64`. These are the `k`-subsets of a universe of
+/// `n` items, the mask counterpart to
+/// [`(0..n).combinations(k)`](crate::Itertools::combinations), walked with
+/// Gosper's hack.
+///
+/// The iterator is double ended, and knows its exact length.
+///
+/// ***Panics*** if `n` is greater than 64.
+///
+/// ```
+/// use itertools::masks_with_popcount;
+///
+/// itertools::assert_equal(masks_with_popcount(4, 2),
+///                         vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+/// itertools::assert_equal(masks_with_popcount(3, 1).rev(), vec![0b100, 0b010, 0b001]);
+/// assert_eq!(masks_with_popcount(64, 32).len(), 1832624140942590534);
+/// ```
+pub fn masks_with_popcount(n: usize, k: usize) -> MasksWithPopcount {
+    assert!(n <= 64, "masks_with_popcount: n must be at most 64, got {}", n);
+    let full = low_bits(n);
+    let first = low_bits(k);
+    MasksWithPopcount {
+        full,
+        front: first,
+        // The same bits, moved to the top
+        back: if k == 0 || k > n { 0 } else { first << (n - k) },
+        remaining: binomial(n as u64, k as u64),
+    }
+}
+
+/// Returns the mask of the lowest `n` bits, for `n` up to 64.
+fn low_bits(n: usize) -> u64 {
+    if n >= 64 {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+/// Returns `binomial(n, k)` for `n` up to 64, which always fits in a `u64`.
+fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    // c = binomial(n - k + i, i), computed wide so each product is exact.
+    (1..=k).fold(1, |c, i| (c as u128 * (n - k + i) as u128 / i as u128) as u64)
+}
+
+/// Returns the next larger mask with as many set bits as `mask`, which must
+/// exist, by Gosper's hack.
+fn next_same_popcount(mask: u64) -> u64 {
+    let lowest = mask & mask.wrapping_neg();
+    // Carry the lowest block of ones up by one bit ...
+    let ripple = mask + lowest;
+    // ... and move the rest of that block back to the bottom.
+    let ones = ((ripple ^ mask) >> 2) / lowest;
+    ripple | ones
+}
+
+impl Iterator for MasksWithPopcount {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let mask = self.front;
+        if self.remaining > 0 {
+            self.front = next_same_popcount(mask);
+        }
+        Some(mask)
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining > usize::MAX as u64 {
+            return (usize::MAX, None);
+        }
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for MasksWithPopcount {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let mask = self.back;
+        if self.remaining > 0 {
+            // Complementing within the `n` bits reverses the order of the masks
+            self.back = self.full ^ next_same_popcount(self.full ^ mask);
+        }
+        Some(mask)
+    }
+}
+
+impl ExactSizeIterator for MasksWithPopcount {}
+
+impl FusedIterator for MasksWithPopcount {}
@@ -0,0 +1,193 @@
+use alloc::vec::Vec;
+use std::fmt;
+use std::iter::{Fuse, FusedIterator};
+
+/// An iterator over the runs of consecutive elements with equal keys, each
+/// collected in a `Vec`.
+///
+/// See [`.chunk_by()`](crate::Itertools::chunk_by) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunkBy<I, K, F>
+    where I: Iterator,
+{
+    iter: Fuse<I>,
+    key: F,
+    // The first element of the next run and its key, once read
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I, K, F> Clone for ChunkBy<I, K, F>
+    where I: Iterator + Clone,
+          I::Item: Clone,
+          K: Clone,
+          F: Clone,
+{
+    clone_fields!(iter, key, pending);
+}
+
+impl<I, K, F> fmt::Debug for ChunkBy<I, K, F>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+          K: fmt::Debug,
+{
+    debug_fmt_fields!(ChunkBy, iter, pending);
+}
+
+/// Create a new `ChunkBy` iterator.
+pub fn chunk_by<I, K, F>(iter: I, key: F) -> ChunkBy<I, K, F>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{
+    ChunkBy {
+        iter: iter.fuse(),
+        key,
+        pending: None,
+    }
+}
+
+impl<I, K, F> Iterator for ChunkBy<I, K, F>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let first = self.iter.next()?;
+                ((self.key)(&first), first)
+            }
+        };
+
+        let mut run = alloc::vec![first];
+        for elt in self.iter.by_ref() {
+            let elt_key = (self.key)(&elt);
+            if elt_key == key {
+                run.push(elt);
+            } else {
+                self.pending = Some((elt_key, elt));
+                break;
+            }
+        }
+        Some((key, run))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.pending.is_some() as usize;
+        let (low, hi) = self.iter.size_hint();
+        // All the elements may be in one run, or each in its own
+        let low = (low > 0 || pending > 0) as usize;
+        (low, hi.and_then(|hi| hi.checked_add(pending)))
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+        where G: FnMut(B, Self::Item) -> B,
+    {
+        let mut key_f = self.key;
+        let run = self.pending.map(|(key, elt)| (key, alloc::vec![elt]));
+        let (acc, run) = self.iter.fold((init, run), |(acc, run), elt| {
+            let elt_key = key_f(&elt);
+            match run {
+                Some((key, mut run)) if key == elt_key => {
+                    run.push(elt);
+                    (acc, Some((key, run)))
+                }
+                Some(run) => (f(acc, run), Some((elt_key, alloc::vec![elt]))),
+                None => (acc, Some((elt_key, alloc::vec![elt]))),
+            }
+        });
+        match run {
+            Some(run) => f(acc, run),
+            None => acc,
+        }
+    }
+}
+
+impl<I, K, F> FusedIterator for ChunkBy<I, K, F>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{}
+
+/// An iterator over the chunks of a fixed number of consecutive elements, each
+/// collected in a `Vec`.
+///
+/// See [`.chunks_vec()`](crate::Itertools::chunks_vec) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunksVec<I> {
+    iter: Fuse<I>,
+    size: usize,
+}
+
+/// Create a new `ChunksVec` iterator.
+///
+/// **Panics** if `size` is 0.
+pub fn chunks_vec<I>(iter: I, size: usize) -> ChunksVec<I>
+    where I: Iterator,
+{
+    assert!(size != 0, "chunk size must be positive");
+    ChunksVec { iter: iter.fuse(), size }
+}
+
+/// Returns the number of chunks of `size` elements, the last one maybe
+/// shorter, that `len` elements make.
+fn chunk_count(len: usize, size: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (len - 1) / size + 1
+    }
+}
+
+impl<I> Iterator for ChunksVec<I>
+    where I: Iterator,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        // Do not trust a huge `size` to be the actual length
+        let rest = self.size - 1;
+        let mut chunk = Vec::with_capacity(1 + rest.min(self.iter.size_hint().0));
+        chunk.push(first);
+        chunk.extend(self.iter.by_ref().take(rest));
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        (chunk_count(low, self.size), hi.map(|hi| chunk_count(hi, self.size)))
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+        where G: FnMut(B, Self::Item) -> B,
+    {
+        let size = self.size;
+        let capacity = size.min(self.iter.size_hint().0);
+        let (acc, chunk) = self.iter.fold((init, Vec::with_capacity(capacity)), |(acc, mut chunk), elt| {
+            chunk.push(elt);
+            if chunk.len() == size {
+                (f(acc, chunk), Vec::with_capacity(capacity))
+            } else {
+                (acc, chunk)
+            }
+        });
+        if chunk.is_empty() {
+            acc
+        } else {
+            f(acc, chunk)
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for ChunksVec<I>
+    where I: ExactSizeIterator,
+{}
+
+impl<I> FusedIterator for ChunksVec<I>
+    where I: Iterator,
+{}
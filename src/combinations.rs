@@ -1,30 +1,41 @@
+use std::cmp;
 use std::fmt;
 
 use super::lazy_buffer::LazyBuffer;
+use super::size_hint;
 use alloc::vec::Vec;
 
 /// An iterator to iterate through all the `k`-length combinations in an iterator.
 ///
+/// Its `nth` skips ahead without building the combinations in between: while
+/// the source has more elements, it only moves the last index, and reads as
+/// much of the source as stepping would. Once the source is exhausted, it jumps
+/// by rank, which takes `O(n + k²)` time for `n` elements: `O(k²)` for the
+/// [`rank`](Combinations::rank) of the current combination, and `O(n)` to
+/// [`unrank`](Combinations::unrank) the target.
+///
 /// See [`.combinations()`](crate::Itertools::combinations) for more information.
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Combinations<I: Iterator> {
     indices: Vec<usize>,
     pool: LazyBuffer<I>,
     first: bool,
+    // Number of combinations left to yield, if split off by `split_at`.
+    limit: Option<usize>,
 }
 
 impl<I> Clone for Combinations<I>
     where I: Clone + Iterator,
           I::Item: Clone,
 {
-    clone_fields!(indices, pool, first);
+    clone_fields!(indices, pool, first, limit);
 }
 
 impl<I> fmt::Debug for Combinations<I>
     where I: Iterator + fmt::Debug,
           I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Combinations, indices, pool, first);
+    debug_fmt_fields!(Combinations, indices, pool, first, limit);
 }
 
 /// Create a new `Combinations` from a clonable iterator.
@@ -38,6 +49,7 @@ pub fn combinations<I>(iter: I, k: usize) -> Combinations<I>
         indices: (0..k).collect(),
         pool,
         first: true,
+        limit: None,
     }
 }
 
@@ -57,6 +69,97 @@ impl<I: Iterator> Combinations<I> {
     #[inline]
     pub(crate) fn src(&self) -> &I { &self.pool.it }
 
+    /// Returns the number of combinations left to yield if the source holds `n`
+    /// elements in total (counting those already in the pool), or `None` if that
+    /// overflows `usize`.
+    pub(crate) fn remaining_for(&self, n: usize) -> Option<usize> {
+        remaining_for(n, &self.indices, self.first)
+    }
+
+    /// Reads the rest of the source iterator into the pool.
+    pub(crate) fn fill_pool(&mut self) {
+        self.pool.prefill(usize::MAX);
+    }
+
+    /// Consumes the rest of the source iterator, returning its total number of
+    /// elements along with the number of combinations left to yield.
+    pub(crate) fn n_and_remaining(self) -> (usize, Option<usize>) {
+        let Combinations { indices, pool, first, limit: _ } = self;
+        let n = pool.len() + pool.it.count();
+        (n, remaining_for(n, &indices, first))
+    }
+
+    /// Returns the lexicographic index of the combination made of the elements at
+    /// positions `indices` of the pool, i.e. the number of combinations a fresh
+    /// iterator yields before it.
+    ///
+    /// The rank depends on the total number of elements, so the source iterator is
+    /// consumed completely.
+    ///
+    /// Returns `None` if `indices` is not a strictly increasing sequence of `k`
+    /// positions into the pool, or if the number of combinations overflows `usize`.
+    ///
+    /// Besides reading the source, this computes `k` binomial coefficients, in
+    /// `O(k²)` time.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..5).combinations(3);
+    /// assert_eq!(it.rank(&[0, 1, 2]), Some(0));
+    /// assert_eq!(it.rank(&[1, 3, 4]), Some(8));
+    /// assert_eq!(it.rank(&[3, 1, 4]), None);
+    /// ```
+    pub fn rank(&mut self, indices: &[usize]) -> Option<usize> {
+        self.pool.prefill(usize::MAX);
+        let n = self.n();
+
+        if indices.len() != self.k()
+            || indices.windows(2).any(|w| w[0] >= w[1])
+            || indices.iter().any(|&i| i >= n)
+        {
+            return None;
+        }
+
+        let total = checked_binomial(n, indices.len())?;
+        Some(rank_indices(n, total, indices))
+    }
+
+    /// Returns the positions in the pool of the elements of the combination with
+    /// lexicographic index `rank`. This is the inverse of [`rank`].
+    ///
+    /// The source iterator is consumed completely.
+    ///
+    /// Returns `None` if `rank` is not smaller than the number of combinations, or if
+    /// that number overflows `usize`.
+    ///
+    /// Besides reading the source, this finds the `k` positions in a single pass
+    /// down the `n` elements, updating a binomial coefficient in constant time at
+    /// each step, in `O(n)` time.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..5).combinations(3);
+    /// assert_eq!(it.unrank(8), Some(vec![1, 3, 4]));
+    /// assert_eq!(it.unrank(10), None);
+    /// ```
+    ///
+    /// [`rank`]: #method.rank
+    pub fn unrank(&mut self, rank: usize) -> Option<Vec<usize>> {
+        self.pool.prefill(usize::MAX);
+        let n = self.n();
+        let total = checked_binomial(n, self.k())?;
+
+        if rank >= total {
+            return None;
+        }
+
+        let mut indices = alloc::vec![0; self.k()];
+        unrank_indices(n, total, rank, &mut indices);
+        Some(indices)
+    }
+
     /// Resets this `Combinations` back to an initial state for combinations of length
     /// `k` over the same pool data source. If `k` is larger than the current length
     /// of the data pool an attempt is made to prefill the pool so that it holds `k`
@@ -78,6 +181,99 @@ impl<I: Iterator> Combinations<I> {
             self.pool.prefill(k);
         }
     }
+
+    /// Moves past the next `steps` combinations without yielding them. Returns
+    /// `false` if that passes the last combination.
+    ///
+    /// Once the source iterator is exhausted, this jumps by rank instead of
+    /// stepping through the combinations one by one.
+    pub(crate) fn skip_ahead(&mut self, steps: usize) -> bool {
+        if steps == 0 {
+            return true;
+        }
+        if self.first && self.k() > self.n() {
+            return false;
+        }
+
+        let moved = if self.first {
+            advance_indices(&mut self.indices, &mut self.pool, steps)
+        } else {
+            increment_indices(&mut self.indices, &mut self.pool)
+                && advance_indices(&mut self.indices, &mut self.pool, steps)
+        };
+
+        // The current combination is pending again, unless the last one is
+        // passed and `indices` stays on it.
+        self.first = moved;
+        moved
+    }
+}
+
+impl<I> Combinations<I>
+    where I: Iterator + Clone,
+          I::Item: Clone,
+{
+    /// Splits the remaining combinations into two iterators: one over the first
+    /// `index` of them, and one over the rest.
+    ///
+    /// The source iterator is consumed completely, and both halves start from
+    /// their position by rank rather than by stepping through the combinations
+    /// before it.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (front, back) = (0..4).combinations(2).split_at(4);
+    /// itertools::assert_equal(front, vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2]]);
+    /// itertools::assert_equal(back, vec![vec![1, 3], vec![2, 3]]);
+    /// ```
+    pub fn split_at(mut self, index: usize) -> (Self, Self) {
+        self.pool.prefill(usize::MAX);
+
+        let mut back = self.clone();
+        let left = self.limit.map_or(index, |limit| limit.min(index));
+        back.limit = back.limit.map(|limit| limit - left);
+        if !back.skip_ahead(index) {
+            back.limit = Some(0);
+        }
+
+        self.limit = Some(left);
+        (self, back)
+    }
+
+    /// Splits the remaining combinations into `n` iterators over consecutive
+    /// ranges of them, whose lengths differ by at most one.
+    ///
+    /// This is convenient to divide the work between `n` threads. The source
+    /// iterator is consumed completely.
+    ///
+    /// **Panics** if `n` is zero, or if the number of combinations left is
+    /// greater than `usize::MAX`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = (0..6).combinations(3).split_into(3);
+    /// let lens: Vec<usize> = parts.iter().map(|part| part.len()).collect();
+    /// assert_eq!(lens, vec![7, 7, 6]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), (0..6).combinations(3));
+    /// ```
+    pub fn split_into(mut self, n: usize) -> Vec<Self> {
+        self.pool.prefill(usize::MAX);
+        let len = self.size_hint().1.expect("Iterator count greater than usize::MAX");
+        crate::split::split_into(self, len, n, Self::split_at)
+    }
+}
+
+impl<I> Combinations<I>
+    where I: Iterator,
+{
+    /// Clones the current combination out of the pool.
+    fn current(&self) -> Vec<I::Item>
+        where I::Item: Clone
+    {
+        self.indices.iter().map(|i| self.pool[*i].clone()).collect()
+    }
 }
 
 impl<I> Iterator for Combinations<I>
@@ -86,39 +282,363 @@ impl<I> Iterator for Combinations<I>
 {
     type Item = Vec<I::Item>;
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return None;
+            }
+            self.limit = Some(limit - 1);
+        }
+
         if self.first {
             if self.k() > self.n() {
                 return None;
             }
             self.first = false;
-        } else if self.indices.is_empty() {
+        } else if !increment_indices(&mut self.indices, &mut self.pool) {
             return None;
+        }
+
+        // Create result vector based on the indices
+        Some(self.current())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if let Some(limit) = self.limit {
+            if n >= limit {
+                self.limit = Some(0);
+                return None;
+            }
+            self.limit = Some(limit - n - 1);
+        }
+
+        let steps = if self.first {
+            if self.k() > self.n() {
+                return None;
+            }
+            self.first = false;
+            n
         } else {
-            // Scan from the end, looking for an index to increment
-            let mut i: usize = self.indices.len() - 1;
+            match n.checked_add(1) {
+                Some(steps) => steps,
+                None if increment_indices(&mut self.indices, &mut self.pool) => n,
+                None => return None,
+            }
+        };
+
+        if steps == 0 || advance_indices(&mut self.indices, &mut self.pool, steps) {
+            Some(self.current())
+        } else {
+            None
+        }
+    }
+
+    fn count(self) -> usize {
+        let limit = self.limit;
+        match (self.n_and_remaining().1, limit) {
+            (Some(count), Some(limit)) => count.min(limit),
+            (Some(count), None) | (None, Some(count)) => count,
+            (None, None) => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = size_hint::add_scalar(self.src().size_hint(), self.n());
+        let low = self.remaining_for(low).unwrap_or(usize::MAX);
+        let high = high.and_then(|n| self.remaining_for(n));
+        match self.limit {
+            Some(limit) => size_hint::min((low, high), (limit, Some(limit))),
+            None => (low, high),
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for Combinations<I>
+    where I: ExactSizeIterator,
+          I::Item: Clone
+{}
+
+/// An iterator to iterate through the `k`-length combinations in an iterator
+/// whose every prefix is accepted by a predicate.
+///
+/// See [`.combinations_pruned()`](crate::Itertools::combinations_pruned) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct CombinationsPruned<I: Iterator, F> {
+    indices: Vec<usize>,
+    pool: LazyBuffer<I>,
+    // The elements at the accepted leading positions of `indices`.
+    prefix: Vec<I::Item>,
+    accept: F,
+    first: bool,
+    done: bool,
+}
+
+impl<I, F> Clone for CombinationsPruned<I, F>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+          F: Clone,
+{
+    clone_fields!(indices, pool, prefix, accept, first, done);
+}
+
+impl<I, F> fmt::Debug for CombinationsPruned<I, F>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(CombinationsPruned, indices, pool, prefix, first, done);
+}
+
+/// Create a new `CombinationsPruned` from a clonable iterator.
+pub fn combinations_pruned<I, F>(iter: I, k: usize, accept: F) -> CombinationsPruned<I, F>
+    where I: Iterator,
+          F: FnMut(&[I::Item]) -> bool,
+{
+    let mut pool = LazyBuffer::new(iter);
+    pool.prefill(k);
 
-            // Check if we need to consume more from the iterator
-            if self.indices[i] == self.pool.len() - 1 {
-                self.pool.get_next(); // may change pool size
+    CombinationsPruned {
+        indices: (0..k).collect(),
+        pool,
+        prefix: Vec::with_capacity(k),
+        accept,
+        first: true,
+        done: false,
+    }
+}
+
+impl<I, F> Iterator for CombinationsPruned<I, F>
+    where I: Iterator,
+          I::Item: Clone,
+          F: FnMut(&[I::Item]) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let k = self.indices.len();
+        let mut from = if self.first {
+            self.first = false;
+            if k > self.pool.len() {
+                self.done = true;
+                return None;
             }
+            0
+        } else {
+            skip_prefix(&mut self.indices, &mut self.pool, k)?
+        };
 
-            while self.indices[i] == i + self.pool.len() - self.indices.len() {
-                if i > 0 {
-                    i -= 1;
-                } else {
-                    // Reached the last combination
-                    return None;
+        'search: loop {
+            // Extend the accepted prefix one element at a time; once a prefix is
+            // rejected, skip every combination starting with it.
+            self.prefix.truncate(from);
+            for len in from + 1..=k {
+                self.prefix.push(self.pool[self.indices[len - 1]].clone());
+                if !(self.accept)(&self.prefix) {
+                    match skip_prefix(&mut self.indices, &mut self.pool, len) {
+                        Some(i) => from = i,
+                        None => break 'search,
+                    }
+                    continue 'search;
                 }
             }
+            return Some(self.prefix.clone());
+        }
+
+        self.done = true;
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        // Any number of the remaining combinations may be pruned.
+        let high = self.pool.it.size_hint().1
+            .and_then(|n| n.checked_add(self.pool.len()))
+            .and_then(|n| remaining_for(n, &self.indices, self.first));
+        (0, high)
+    }
+}
+
+/// Counts the combinations of `0..n` that come after `indices` in lexicographic
+/// order (or from `indices` on, if it was not yielded yet), or `None` on overflow.
+///
+/// Those that first differ from `indices` at position `i` pick a larger index
+/// there and then `k - i - 1` more after it, `C(n - 1 - indices[i], k - i)` ways
+/// in total by the hockey-stick identity.
+fn remaining_for(n: usize, indices: &[usize], first: bool) -> Option<usize> {
+    let k = indices.len();
+    if first && k > n {
+        return Some(0);
+    }
+
+    let after = indices.iter().enumerate().try_fold(0usize, |acc, (i, &index)| {
+        acc.checked_add(checked_binomial(n - 1 - index, k - i)?)
+    });
+    if first { after?.checked_add(1) } else { after }
+}
+
+/// Moves `indices` to the next combination, consuming more of the source
+/// iterator if needed. Returns `false` once the last combination is passed.
+pub(crate) fn increment_indices<I: Iterator>(indices: &mut [usize], pool: &mut LazyBuffer<I>) -> bool {
+    skip_prefix(indices, pool, indices.len()).is_some()
+}
+
+/// Moves `indices` to the next combination that does not start with the first
+/// `len` of them, consuming more of the source iterator if needed.
+///
+/// Returns the position of the index that was incremented (the ones to its left
+/// are unchanged), or `None` once the last combination is passed.
+fn skip_prefix<I: Iterator>(indices: &mut [usize], pool: &mut LazyBuffer<I>, len: usize) -> Option<usize> {
+    let k = indices.len();
+    if len == 0 {
+        return None;
+    }
+
+    // Scan from the end of the prefix, looking for an index to increment
+    let mut i: usize = len - 1;
+
+    // Check if we need to consume more from the iterator
+    if indices[i] == i + pool.len() - k {
+        pool.get_next(); // may change pool size
+    }
+
+    while indices[i] == i + pool.len() - k {
+        if i > 0 {
+            i -= 1;
+        } else {
+            // Reached the last combination
+            return None;
+        }
+    }
+
+    // Increment index, and reset the ones to its right
+    indices[i] += 1;
+    for j in i+1..k {
+        indices[j] = indices[j - 1] + 1;
+    }
+    Some(i)
+}
+
+/// Moves `indices` forward by `steps` combinations. Returns `false` if the
+/// last combination is passed on the way.
+pub(crate) fn advance_indices<I: Iterator>(indices: &mut [usize], pool: &mut LazyBuffer<I>, steps: usize) -> bool {
+    let k = indices.len();
+    if k == 0 {
+        return steps == 0;
+    }
 
-            // Increment index, and reset the ones to its right
-            self.indices[i] += 1;
-            for j in i+1..self.indices.len() {
-                self.indices[j] = self.indices[j - 1] + 1;
+    // As long as only the last index moves, the pool grows exactly as far as
+    // it would when stepping one combination at a time.
+    let last = indices[k - 1];
+    let needed = last.saturating_add(steps).saturating_add(1);
+    pool.prefill(needed);
+    if pool.len() >= needed {
+        indices[k - 1] += steps;
+        return true;
+    }
+
+    // The source is exhausted, so `n` is known and we can jump by rank.
+    let n = pool.len();
+    match checked_binomial(n, k) {
+        Some(total) => {
+            match rank_indices(n, total, indices).checked_add(steps) {
+                Some(target) if target < total => {
+                    unrank_indices(n, total, target, indices);
+                    true
+                }
+                _ => {
+                    // Park on the last combination so `next` keeps returning `None`.
+                    for (i, index) in indices.iter_mut().enumerate() {
+                        *index = n - k + i;
+                    }
+                    false
+                }
             }
         }
+        None => (0..steps).all(|_| increment_indices(indices, pool)),
+    }
+}
 
-        // Create result vector based on the indices
-        Some(self.indices.iter().map(|i| self.pool[*i].clone()).collect())
+/// Computes the binomial coefficient `C(n, k)`, or `None` if it overflows `usize`.
+pub(crate) fn checked_binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = cmp::min(k, n - k);
+    let mut c: usize = 1;
+    for i in 1..=k {
+        // c = C(n - k + i - 1, i - 1); since c * (n - k + i) is divisible by i,
+        // dividing out their common factor first keeps the product exact.
+        let g = gcd(c, i);
+        c = (c / g).checked_mul((n - k + i) / (i / g))?;
     }
+    Some(c)
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// Returns the lexicographic rank of the combination `indices` of `0..n`, where
+/// `total` is the number of such combinations.
+///
+/// This goes through the combinatorial number system: the complementary
+/// combination `d[i] = n - 1 - indices[k - 1 - i]` has colexicographic rank
+/// `sum(C(d[i], i + 1))`, and reversing the order of `0..n` reverses the rank.
+fn rank_indices(n: usize, total: usize, indices: &[usize]) -> usize {
+    let colex = indices.iter().rev().enumerate().fold(0, |acc, (i, &index)| {
+        // Every partial sum is below `total`, so none of this overflows.
+        acc + checked_binomial(n - 1 - index, i + 1).unwrap()
+    });
+    total - 1 - colex
+}
+
+/// Writes the combination of `0..n` with lexicographic rank `rank` into `indices`.
+/// This is the inverse of `rank_indices`, picking each complementary index greedily.
+///
+/// The complementary indices are found from the largest down, so a single scan down
+/// from `n` finds all of them, updating the binomial coefficient at each step with
+/// `C(d - 1, r) = C(d, r) * (d - r) / d`, and from one index to the next with
+/// `C(d - 1, r - 1) = C(d, r) * r / d`. This takes `O(n)` time.
+fn unrank_indices(n: usize, total: usize, rank: usize, indices: &mut [usize]) {
+    let k = indices.len();
+    if k == 0 {
+        return;
+    }
+    let mut colex = total - 1 - rank;
+
+    // `c` is `C(d, r)`, starting from `C(n - 1, k)`.
+    let mut d = n - 1;
+    let mut c = mul_div(total, n - k, n);
+    for r in (1..=k).rev() {
+        // Find the largest `d` below the previous one with `C(d, r) <= colex`.
+        // `C(r - 1, r)` is 0, so this stops at `r - 1` at the latest.
+        while c > colex {
+            c = mul_div(c, d - r, d);
+            d -= 1;
+        }
+
+        colex -= c;
+        indices[k - r] = n - 1 - d;
+        if r > 1 {
+            c = mul_div(c, r, d);
+            d -= 1;
+        }
+    }
+}
+
+/// Computes `a * b / c` for a quotient that is exact and fits in `usize`, even when
+/// `a * b` does not.
+fn mul_div(a: usize, b: usize, c: usize) -> usize {
+    (a as u128 * b as u128 / c as u128) as usize
 }
@@ -1,7 +1,9 @@
 use alloc::vec::Vec;
 use std::fmt;
 
+use super::combinations::checked_binomial;
 use super::lazy_buffer::LazyBuffer;
+use super::size_hint;
 
 /// An iterator to iterate through all the `n`-length combinations in an iterator, with replacement.
 ///
@@ -99,4 +101,49 @@ where
             None => None,
         }
     }
+
+    fn count(self) -> usize {
+        let CombinationsWithReplacement { indices, pool, first } = self;
+        let n = pool.len() + pool.it.count();
+        match remaining_for(n, &indices, first) {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+        let low = remaining_for(low, &self.indices, self.first).unwrap_or(usize::MAX);
+        let high = high.and_then(|n| remaining_for(n, &self.indices, self.first));
+        (low, high)
+    }
+}
+
+impl<I> ExactSizeIterator for CombinationsWithReplacement<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{}
+
+/// Counts the combinations with replacement of `0..n` that come after `indices`
+/// in lexicographic order (or from `indices` on, if it was not yielded yet), or
+/// `None` on overflow.
+///
+/// Those that first differ from `indices` at position `i` pick a larger index
+/// there and then `k - i - 1` more that are at least as large, which by the
+/// hockey-stick identity makes `C(n - indices[i] + k - i - 2, k - i)` of them.
+fn remaining_for(n: usize, indices: &[usize], first: bool) -> Option<usize> {
+    let k = indices.len();
+    if first {
+        // The number of multisets of size `k` drawn from `n` elements
+        return match (n, k) {
+            (_, 0) => Some(1),
+            (0, _) => Some(0),
+            _ => checked_binomial(n.checked_add(k - 1)?, k),
+        };
+    }
+
+    indices.iter().enumerate().try_fold(0usize, |acc, (i, &index)| {
+        acc.checked_add(checked_binomial((n - index - 1).checked_add(k - i - 1)?, k - i)?)
+    })
 }
@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+use std::iter::FromIterator;
+
+use crate::k_smallest::k_smallest;
+use crate::map_kind::{BTreeMapKind, MapKind};
+
+/// A frequency table: the number of times each item was counted, kept in a
+/// map of kind `M`.
+///
+/// On top of the counts of each item, this keeps their total, and finds the
+/// most and least common items in `O(n log k)` time.
+///
+/// See [`.into_counts()`](crate::Itertools::into_counts) and
+/// [`.into_counts_in()`](crate::Itertools::into_counts_in).
+///
+/// ```
+/// use itertools::Itertools;
+///
+/// let counts = "abracadabra".chars().into_counts();
+///
+/// assert_eq!(counts.get(&'a'), 5);
+/// assert_eq!(counts.get(&'z'), 0);
+/// assert_eq!(counts.total(), 11);
+/// assert_eq!(counts.most_common(1), vec![(&'a', 5)]);
+/// ```
+pub struct Counts<K, M>
+    where M: MapKind<K, usize>,
+{
+    kind: M,
+    map: M::Map,
+    total: usize,
+}
+
+/// A frequency table that keeps its items in order, in a `BTreeMap`.
+///
+/// The most and least common items that have equal counts come in the order of
+/// the items too.
+///
+/// See [`.into_counts_in()`](crate::Itertools::into_counts_in).
+pub type OrderedCounts<K> = Counts<K, BTreeMapKind>;
+
+impl<K, M> Clone for Counts<K, M>
+    where M: MapKind<K, usize> + Clone,
+          M::Map: Clone,
+{
+    clone_fields!(kind, map, total);
+}
+
+impl<K, M> fmt::Debug for Counts<K, M>
+    where M: MapKind<K, usize>,
+          M::Map: fmt::Debug,
+{
+    debug_fmt_fields!(Counts, map, total);
+}
+
+impl<K, M> Default for Counts<K, M>
+    where M: MapKind<K, usize> + Default,
+{
+    fn default() -> Self {
+        Counts::new_in(M::default())
+    }
+}
+
+// An entry of the table, ordered by its count and then by its place in the
+// iteration of the map, but not by its item.
+struct Frequency<'a, K, C> {
+    count: C,
+    index: usize,
+    item: &'a K,
+}
+
+impl<'a, K, C: Ord> Frequency<'a, K, C> {
+    fn order(&self) -> (&C, usize) {
+        (&self.count, self.index)
+    }
+}
+
+impl<'a, K, C: Ord> PartialEq for Frequency<'a, K, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.order() == other.order()
+    }
+}
+
+impl<'a, K, C: Ord> Eq for Frequency<'a, K, C> {}
+
+impl<'a, K, C: Ord> PartialOrd for Frequency<'a, K, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, K, C: Ord> Ord for Frequency<'a, K, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order().cmp(&other.order())
+    }
+}
+
+impl<K, M> Counts<K, M>
+    where M: MapKind<K, usize>,
+{
+    /// Creates an empty table, that keeps its counts in a map of kind `kind`.
+    pub fn new_in(kind: M) -> Self {
+        Counts {
+            map: kind.new_map(),
+            kind,
+            total: 0,
+        }
+    }
+
+    /// Counts `item` once more.
+    pub fn push(&mut self, item: K) {
+        self.push_n(item, 1)
+    }
+
+    /// Counts `item` `n` times more.
+    pub fn push_n(&mut self, item: K, n: usize) {
+        if n > 0 {
+            *self.kind.entry_or_insert_with(&mut self.map, item, || 0) += n;
+            self.total += n;
+        }
+    }
+
+    /// Returns the number of times `item` was counted.
+    pub fn get(&self, item: &K) -> usize {
+        self.kind.get(&self.map, item).cloned().unwrap_or(0)
+    }
+
+    /// Returns the sum of all the counts.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of distinct items.
+    pub fn len(&self) -> usize {
+        self.kind.len(&self.map)
+    }
+
+    /// Returns `true` if nothing was counted.
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Returns the map of the items to their counts.
+    pub fn as_map(&self) -> &M::Map {
+        &self.map
+    }
+
+    /// Returns the map of the items to their counts.
+    pub fn into_map(self) -> M::Map {
+        self.map
+    }
+
+    /// Adds the counts of `other` to these ones.
+    pub fn merge<N>(&mut self, other: Counts<K, N>)
+        where N: MapKind<K, usize>,
+              N::Map: IntoIterator<Item = (K, usize)>,
+    {
+        for (item, n) in other.map {
+            self.push_n(item, n);
+        }
+    }
+
+    /// Returns the `k` most common items and their counts, from the most
+    /// common one.
+    ///
+    /// If less than `k` items were counted, this returns all of them. Items
+    /// with equal counts come in the order of the map.
+    ///
+    /// This takes `O(n log k)` time, with `n` the number of distinct items.
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let counts = "mississippi".chars().into_counts_in(BTreeMapKind);
+    /// assert_eq!(counts.most_common(2), vec![(&'i', 4), (&'s', 4)]);
+    /// assert_eq!(counts.least_common(2), vec![(&'m', 1), (&'p', 2)]);
+    /// ```
+    pub fn most_common<'a>(&'a self, k: usize) -> Vec<(&'a K, usize)>
+        where &'a M::Map: IntoIterator<Item = (&'a K, &'a usize)>,
+    {
+        let frequencies = self.map.into_iter().enumerate()
+            .map(|(index, (item, &n))| Frequency { count: Reverse(n), index, item });
+        k_smallest(frequencies, k).into_sorted_vec().into_iter()
+            .map(|f| (f.item, f.count.0))
+            .collect()
+    }
+
+    /// Returns the `k` least common items and their counts, from the least
+    /// common one.
+    ///
+    /// If less than `k` items were counted, this returns all of them. Items
+    /// with equal counts come in the order of the map.
+    ///
+    /// This takes `O(n log k)` time, with `n` the number of distinct items.
+    pub fn least_common<'a>(&'a self, k: usize) -> Vec<(&'a K, usize)>
+        where &'a M::Map: IntoIterator<Item = (&'a K, &'a usize)>,
+    {
+        let frequencies = self.map.into_iter().enumerate()
+            .map(|(index, (item, &n))| Frequency { count: n, index, item });
+        k_smallest(frequencies, k).into_sorted_vec().into_iter()
+            .map(|f| (f.item, f.count))
+            .collect()
+    }
+}
+
+impl<K, M> Extend<K> for Counts<K, M>
+    where M: MapKind<K, usize>,
+{
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|item| self.push(item))
+    }
+}
+
+impl<K, M> FromIterator<K> for Counts<K, M>
+    where M: MapKind<K, usize> + Default,
+{
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let mut counts = Counts::default();
+        counts.extend(iter);
+        counts
+    }
+}
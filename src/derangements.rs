@@ -0,0 +1,187 @@
+use alloc::vec::Vec;
+use std::fmt;
+
+use super::lazy_buffer::LazyBuffer;
+use super::size_hint;
+
+/// An iterator adaptor that iterates through the derangements of the elements
+/// from an iterator.
+///
+/// See [`.derangements()`](crate::Itertools::derangements) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Derangements<I: Iterator> {
+    pool: LazyBuffer<I>,
+    // The current derangement, as positions into the pool.
+    indices: Vec<usize>,
+    // Whether each position of the pool is used by `indices`.
+    used: Vec<bool>,
+    first: bool,
+    done: bool,
+    // Derangements left to yield once the pool is complete, `None` on overflow.
+    remaining: Option<usize>,
+}
+
+impl<I> Clone for Derangements<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(pool, indices, used, first, done, remaining);
+}
+
+impl<I> fmt::Debug for Derangements<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(Derangements, pool, indices, used, first, done, remaining);
+}
+
+/// Create a new `Derangements` from an iterator.
+pub fn derangements<I>(iter: I) -> Derangements<I>
+    where I: Iterator,
+{
+    Derangements {
+        pool: LazyBuffer::new(iter),
+        indices: Vec::new(),
+        used: Vec::new(),
+        first: true,
+        done: false,
+        remaining: None,
+    }
+}
+
+impl<I: Iterator> Derangements<I> {
+    /// Reads the whole source and moves to the first derangement.
+    fn start(&mut self) {
+        self.first = false;
+        self.pool.prefill(usize::MAX);
+
+        let n = self.pool.len();
+        self.used = alloc::vec![false; n];
+        self.remaining = count_derangements(n);
+        self.done = !self.fill();
+    }
+
+    /// Returns whether position `pos` of the derangement, after the ones in
+    /// `indices`, can take element `value` and still be completed.
+    fn allowed(&self, pos: usize, value: usize) -> bool {
+        let n = self.pool.len();
+        if self.used[value] || value == pos {
+            return false;
+        }
+
+        // Any free elements can be deranged into two or more free positions, so
+        // the only dead end is leaving the last element for the last position.
+        !(pos + 2 == n && value != n - 1 && !self.used[n - 1])
+    }
+
+    /// Fills `indices` up to the pool length with the smallest allowed elements.
+    /// Returns `false` if there is no derangement at all.
+    fn fill(&mut self) -> bool {
+        let n = self.pool.len();
+        while self.indices.len() < n {
+            let pos = self.indices.len();
+            match (0..n).find(|&value| self.allowed(pos, value)) {
+                Some(value) => {
+                    self.used[value] = true;
+                    self.indices.push(value);
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Moves to the next derangement in lexicographic order. Returns `false` if
+    /// the current one was the last.
+    fn advance(&mut self) -> bool {
+        let n = self.pool.len();
+
+        // Scan from the end, looking for a position that can take a larger element
+        while let Some(old) = self.indices.pop() {
+            self.used[old] = false;
+            let pos = self.indices.len();
+            if let Some(value) = (old + 1..n).find(|&value| self.allowed(pos, value)) {
+                self.used[value] = true;
+                self.indices.push(value);
+                // Reset the positions after it to the smallest derangement
+                return self.fill();
+            }
+        }
+
+        false
+    }
+}
+
+impl<I> Iterator for Derangements<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.start();
+        } else if !self.done && !self.advance() {
+            self.done = true;
+        }
+
+        if self.done {
+            return None;
+        }
+
+        self.remaining = self.remaining.map(|r| r - 1);
+        Some(self.indices.iter().map(|&i| self.pool[i].clone()).collect())
+    }
+
+    fn count(mut self) -> usize {
+        if self.first {
+            self.start();
+        }
+
+        if self.done {
+            return 0;
+        }
+
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        if self.first {
+            // The number of derangements only grows with the number of elements
+            // from one element on, and an empty source has one.
+            let (low, high) = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+            let low = if low >= 2 { count_derangements(low).unwrap_or(usize::MAX) } else { 0 };
+            let high = high.and_then(|n| if n >= 2 { count_derangements(n) } else { Some(1) });
+            return (low, high);
+        }
+
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Counts the derangements of `n` elements, or `None` on overflow.
+///
+/// This uses the recurrence `D(n) = (n - 1) * (D(n - 1) + D(n - 2))`.
+fn count_derangements(n: usize) -> Option<usize> {
+    let (mut prev, mut cur): (usize, usize) = (1, 0);
+    if n == 0 {
+        return Some(prev);
+    }
+
+    for m in 2..=n {
+        let next = prev.checked_add(cur)?.checked_mul(m - 1)?;
+        prev = cur;
+        cur = next;
+    }
+    Some(cur)
+}
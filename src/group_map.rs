@@ -1,6 +1,10 @@
-#![cfg(feature = "use_std")]
+#![cfg(feature = "use_alloc")]
 
+use alloc::vec::Vec;
+use crate::map_kind::{MapKind, NestedMapKind};
+#[cfg(feature = "use_std")]
 use std::collections::HashMap;
+#[cfg(feature = "use_std")]
 use std::hash::Hash;
 use std::iter::Iterator;
 
@@ -8,6 +12,7 @@ use std::iter::Iterator;
 ///
 /// See [`.into_group_map()`](crate::Itertools::into_group_map)
 /// for more information.
+#[cfg(feature = "use_std")]
 pub fn into_group_map<I, K, V>(iter: I) -> HashMap<K, Vec<V>>
     where I: Iterator<Item=(K, V)>,
           K: Hash + Eq,
@@ -21,6 +26,7 @@ pub fn into_group_map<I, K, V>(iter: I) -> HashMap<K, Vec<V>>
     lookup
 }
 
+#[cfg(feature = "use_std")]
 pub fn into_group_map_by<I, K, V>(iter: I, f: impl Fn(&V) -> K) -> HashMap<K, Vec<V>>
     where
         I: Iterator<Item=V>,
@@ -30,3 +36,124 @@ pub fn into_group_map_by<I, K, V>(iter: I, f: impl Fn(&V) -> K) -> HashMap<K, Ve
         iter.map(|v| (f(&v), v))
     )
 }
+
+/// Return a map of the given kind, of keys mapped to a list of their
+/// corresponding values.
+///
+/// See [`.into_group_map_in()`](crate::Itertools::into_group_map_in)
+/// for more information.
+pub fn into_group_map_in<I, K, V, M>(iter: I, kind: M) -> M::Map
+    where I: Iterator<Item=(K, V)>,
+          M: MapKind<K, Vec<V>>,
+{
+    let mut lookup = kind.new_map();
+
+    iter.for_each(|(key, val)| {
+        kind.entry_or_insert_with(&mut lookup, key, Vec::new).push(val);
+    });
+
+    lookup
+}
+
+pub fn into_group_map_by_in<I, K, V, M>(iter: I, kind: M, f: impl Fn(&V) -> K) -> M::Map
+    where
+        I: Iterator<Item=V>,
+        M: MapKind<K, Vec<V>>,
+{
+    into_group_map_in(
+        iter.map(|v| (f(&v), v)),
+        kind,
+    )
+}
+
+/// A tuple of keying functions for
+/// [`.into_nested_group_map_by_in()`](crate::Itertools::into_nested_group_map_by_in),
+/// one for each level of maps, with maps of kind `M` at each level.
+///
+/// This is implemented for tuples of two to four `FnMut(&V) -> K` closures.
+pub trait NestedGroupKeys<V, M> {
+    /// The keys of an element, as nested pairs such as `((k1, k2), k3)`.
+    type Key;
+
+    /// The kind of the nested maps.
+    type Kind;
+
+    /// Returns the keys of `val`.
+    fn keys(&mut self, val: &V) -> Self::Key;
+
+    /// Returns the kind of the nested maps, with maps of kind `kind` at each
+    /// level.
+    fn nested_kind(kind: M) -> Self::Kind;
+}
+
+impl<V, M, A, B, KA, KB> NestedGroupKeys<V, M> for (A, B)
+    where A: FnMut(&V) -> KA,
+          B: FnMut(&V) -> KB,
+          M: Clone,
+{
+    type Key = (KA, KB);
+    type Kind = NestedMapKind<M, M>;
+
+    fn keys(&mut self, val: &V) -> Self::Key {
+        ((self.0)(val), (self.1)(val))
+    }
+
+    fn nested_kind(kind: M) -> Self::Kind {
+        NestedMapKind::new(kind.clone(), kind)
+    }
+}
+
+impl<V, M, A, B, C, KA, KB, KC> NestedGroupKeys<V, M> for (A, B, C)
+    where A: FnMut(&V) -> KA,
+          B: FnMut(&V) -> KB,
+          C: FnMut(&V) -> KC,
+          M: Clone,
+{
+    type Key = ((KA, KB), KC);
+    type Kind = NestedMapKind<NestedMapKind<M, M>, M>;
+
+    fn keys(&mut self, val: &V) -> Self::Key {
+        (((self.0)(val), (self.1)(val)), (self.2)(val))
+    }
+
+    fn nested_kind(kind: M) -> Self::Kind {
+        NestedMapKind::new(NestedMapKind::new(kind.clone(), kind.clone()), kind)
+    }
+}
+
+impl<V, M, A, B, C, D, KA, KB, KC, KD> NestedGroupKeys<V, M> for (A, B, C, D)
+    where A: FnMut(&V) -> KA,
+          B: FnMut(&V) -> KB,
+          C: FnMut(&V) -> KC,
+          D: FnMut(&V) -> KD,
+          M: Clone,
+{
+    type Key = (((KA, KB), KC), KD);
+    type Kind = NestedMapKind<NestedMapKind<NestedMapKind<M, M>, M>, M>;
+
+    fn keys(&mut self, val: &V) -> Self::Key {
+        ((((self.0)(val), (self.1)(val)), (self.2)(val)), (self.3)(val))
+    }
+
+    fn nested_kind(kind: M) -> Self::Kind {
+        let inner = NestedMapKind::new(kind.clone(), kind.clone());
+        NestedMapKind::new(NestedMapKind::new(inner, kind.clone()), kind)
+    }
+}
+
+/// Return nested maps of the given kind, of keys mapped to lists of their
+/// corresponding values at the innermost level.
+///
+/// See [`.into_nested_group_map_by_in()`](crate::Itertools::into_nested_group_map_by_in)
+/// for more information.
+pub fn into_nested_group_map_by_in<I, M, G>(iter: I, kind: M, mut keys: G)
+    -> <G::Kind as MapKind<G::Key, Vec<I::Item>>>::Map
+    where I: Iterator,
+          G: NestedGroupKeys<I::Item, M>,
+          G::Kind: MapKind<G::Key, Vec<I::Item>>,
+{
+    into_group_map_in(
+        iter.map(|v| (keys.keys(&v), v)),
+        G::nested_kind(kind),
+    )
+}
@@ -0,0 +1,181 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::groupbylazy::{ChunkIndex, GroupInner};
+
+// The state is shared like in `GroupBy`, and the lock ignores poisoning just
+// as a `RefCell` would after a panic in the key function.
+fn lock<T>(inner: &Mutex<T>) -> MutexGuard<'_, T> {
+    inner.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An iterator over the groups of consecutive elements with equal keys, that
+/// can be sent to and consumed from other threads.
+///
+/// This is the thread safe counterpart to [`GroupBy`](crate::structs::GroupBy):
+/// its groups share the underlying iterator behind a `Mutex` rather than a
+/// `RefCell`, and own their share of it, so they are `Send` and need not be
+/// consumed in order. Like with `GroupBy`, the elements of a group are only
+/// buffered if a later group is requested before they are all consumed.
+///
+/// Iterator element type is `(K, GroupSync)`: the group's key and the group
+/// iterator.
+///
+/// See [`.group_by_sync()`](crate::Itertools::group_by_sync) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct GroupBySync<K, I, F>
+    where I: Iterator,
+{
+    inner: Arc<Mutex<GroupInner<K, I, F>>>,
+    // the index of the next group
+    index: usize,
+}
+
+/// Create a new `GroupBySync` iterator.
+pub fn group_by_sync<K, J, F>(iter: J, f: F) -> GroupBySync<K, J::IntoIter, F>
+    where J: IntoIterator,
+          F: FnMut(&J::Item) -> K,
+{
+    GroupBySync {
+        inner: Arc::new(Mutex::new(GroupInner::new(f, iter.into_iter()))),
+        index: 0,
+    }
+}
+
+impl<K, I, F> Iterator for GroupBySync<K, I, F>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{
+    type Item = (K, GroupSync<K, I, F>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        self.index += 1;
+        let inner = &mut *lock(&self.inner);
+        inner.step(index).map(|elt| {
+            let key = inner.group_key(index);
+            (key, GroupSync {
+                parent: Arc::clone(&self.inner),
+                index,
+                first: Some(elt),
+            })
+        })
+    }
+}
+
+/// An iterator for the elements in a single group of a
+/// [`GroupBySync`](crate::structs::GroupBySync).
+///
+/// Iterator element type is `I::Item`.
+pub struct GroupSync<K, I, F>
+    where I: Iterator,
+{
+    parent: Arc<Mutex<GroupInner<K, I, F>>>,
+    index: usize,
+    first: Option<I::Item>,
+}
+
+impl<K, I, F> Drop for GroupSync<K, I, F>
+    where I: Iterator,
+{
+    fn drop(&mut self) {
+        lock(&self.parent).drop_group(self.index);
+    }
+}
+
+impl<K, I, F> Iterator for GroupSync<K, I, F>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let elt @ Some(..) = self.first.take() {
+            return elt;
+        }
+        lock(&self.parent).step(self.index)
+    }
+}
+
+/// An iterator over the chunks of a fixed number of consecutive elements,
+/// that can be sent to and consumed from other threads.
+///
+/// This is the thread safe counterpart to
+/// [`IntoChunks`](crate::structs::IntoChunks), in the same way as
+/// [`GroupBySync`](crate::structs::GroupBySync) is to `GroupBy`.
+///
+/// Iterator element type is `ChunkSync`, each chunk's iterator.
+///
+/// See [`.chunks_sync()`](crate::Itertools::chunks_sync) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunksSync<I>
+    where I: Iterator,
+{
+    inner: Arc<Mutex<GroupInner<usize, I, ChunkIndex>>>,
+    // the index of the next chunk
+    index: usize,
+}
+
+/// Create a new `ChunksSync` iterator.
+pub fn chunks_sync<J>(iter: J, size: usize) -> ChunksSync<J::IntoIter>
+    where J: IntoIterator,
+{
+    ChunksSync {
+        inner: Arc::new(Mutex::new(GroupInner::new(ChunkIndex::new(size), iter.into_iter()))),
+        index: 0,
+    }
+}
+
+impl<I> Iterator for ChunksSync<I>
+    where I: Iterator,
+{
+    type Item = ChunkSync<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        self.index += 1;
+        lock(&self.inner).step(index).map(|elt| {
+            ChunkSync {
+                parent: Arc::clone(&self.inner),
+                index,
+                first: Some(elt),
+            }
+        })
+    }
+}
+
+/// An iterator for the elements in a single chunk of a
+/// [`ChunksSync`](crate::structs::ChunksSync).
+///
+/// Iterator element type is `I::Item`.
+pub struct ChunkSync<I>
+    where I: Iterator,
+{
+    parent: Arc<Mutex<GroupInner<usize, I, ChunkIndex>>>,
+    index: usize,
+    first: Option<I::Item>,
+}
+
+impl<I> Drop for ChunkSync<I>
+    where I: Iterator,
+{
+    fn drop(&mut self) {
+        lock(&self.parent).drop_group(self.index);
+    }
+}
+
+impl<I> Iterator for ChunkSync<I>
+    where I: Iterator,
+{
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let elt @ Some(..) = self.first.take() {
+            return elt;
+        }
+        lock(&self.parent).step(self.index)
+    }
+}
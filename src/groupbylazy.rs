@@ -1,8 +1,11 @@
 use std::cell::{Cell, RefCell};
+#[cfg(feature = "use_std")]
+use std::error::Error;
+use std::fmt;
 use alloc::vec::{self, Vec};
 
 /// A trait to unify FnMut for GroupBy with the chunk key in IntoChunks
-trait KeyFunction<A> {
+pub(crate) trait KeyFunction<A> {
     type Key;
     fn call_mut(&mut self, arg: A) -> Self::Key;
 }
@@ -18,9 +21,63 @@ impl<'a, A, K, F: ?Sized> KeyFunction<A> for F
 }
 
 
+/// What a [`GroupBy`] or [`IntoChunks`] does when buffering the elements of
+/// a skipped group would exceed its buffer limit.
+///
+/// See [`GroupBy::with_buffer_limit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferLimitPolicy {
+    /// Panic.
+    Panic,
+    /// Discard the rest of the skipped group, and report it with an error from
+    /// the group's `try_next`.
+    Error,
+    /// Silently discard the rest of the skipped group.
+    Discard,
+}
+
+/// The error returned by the `try_next` method of a group, or chunk, whose
+/// elements were discarded because they did not fit in the buffer.
+///
+/// See [`BufferLimitPolicy::Error`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferLimitError {
+    limit: usize,
+}
+
+impl BufferLimitError {
+    /// Returns the buffer limit that was exceeded.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for BufferLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "group elements discarded past the buffer limit of {}", self.limit)
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl Error for BufferLimitError {}
+
+/// Statistics about the buffering of a [`GroupBy`] or [`IntoChunks`].
+///
+/// See [`GroupBy::buffer_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    /// The number of elements buffered now.
+    pub buffered: usize,
+    /// The largest number of elements buffered at once so far.
+    pub peak_buffered: usize,
+    /// The number of groups whose elements were discarded because of the
+    /// buffer limit.
+    pub discarded_groups: usize,
+}
+
 /// ChunkIndex acts like the grouping key function for IntoChunks
 #[derive(Debug)]
-struct ChunkIndex {
+pub(crate) struct ChunkIndex {
     size: usize,
     index: usize,
     key: usize,
@@ -28,7 +85,7 @@ struct ChunkIndex {
 
 impl ChunkIndex {
     #[inline(always)]
-    fn new(size: usize) -> Self {
+    pub(crate) fn new(size: usize) -> Self {
         ChunkIndex {
             size,
             index: 0,
@@ -51,7 +108,7 @@ impl<'a, A> KeyFunction<A> for ChunkIndex {
 }
 
 
-struct GroupInner<K, I, F>
+pub(crate) struct GroupInner<K, I, F>
     where I: Iterator
 {
     key: F,
@@ -72,6 +129,13 @@ struct GroupInner<K, I, F>
     buffer: Vec<vec::IntoIter<I::Item>>,
     /// index of last group iter that was dropped, usize::MAX == none
     dropped_group: usize,
+    /// Most elements to buffer at once, and what to do past that
+    buffer_limit: usize,
+    buffer_policy: BufferLimitPolicy,
+    /// Groups discarded under `BufferLimitPolicy::Error` that have not
+    /// reported it yet
+    discarded: Vec<usize>,
+    stats: BufferStats,
 }
 
 impl<K, I, F> GroupInner<K, I, F>
@@ -81,7 +145,25 @@ impl<K, I, F> GroupInner<K, I, F>
 {
     /// `client`: Index of group that requests next element
     #[inline(always)]
-    fn step(&mut self, client: usize) -> Option<I::Item> {
+    pub(crate) fn step(&mut self, client: usize) -> Option<I::Item> {
+        self.try_step(client).unwrap_or(None)
+    }
+
+    /// Like `step`, but fails once for a group whose elements were
+    /// discarded under `BufferLimitPolicy::Error`.
+    #[inline(always)]
+    pub(crate) fn try_step(&mut self, client: usize) -> Result<Option<I::Item>, BufferLimitError> {
+        if !self.discarded.is_empty() {
+            if let Some(i) = self.discarded.iter().position(|&group| group == client) {
+                self.discarded.swap_remove(i);
+                return Err(BufferLimitError { limit: self.buffer_limit });
+            }
+        }
+        Ok(self.step_inner(client))
+    }
+
+    #[inline(always)]
+    fn step_inner(&mut self, client: usize) -> Option<I::Item> {
         /*
         println!("client={}, bottom_group={}, oldest_buffered_group={}, top_group={}, buffers=[{}]",
                  client, self.bottom_group, self.oldest_buffered_group,
@@ -112,6 +194,9 @@ impl<K, I, F> GroupInner<K, I, F>
             return None;
         }
         let elt = self.buffer.get_mut(bufidx).and_then(|queue| queue.next());
+        if elt.is_some() {
+            self.stats.buffered -= 1;
+        }
         if elt.is_none() && client == self.oldest_buffered_group {
             // FIXME: VecDeque is unfortunately not zero allocation when empty,
             // so we do this job manually.
@@ -159,10 +244,11 @@ impl<K, I, F> GroupInner<K, I, F>
         // each group index, client is the next index efter top_group.
         debug_assert!(self.top_group + 1 == client);
         let mut group = Vec::new();
+        let mut keep = self.top_group != self.dropped_group;
 
         if let Some(elt) = self.current_elt.take() {
-            if self.top_group != self.dropped_group {
-                group.push(elt);
+            if keep {
+                keep = self.buffer_elt(&mut group, elt);
             }
         }
         let mut first_elt = None; // first element of the next group
@@ -178,12 +264,12 @@ impl<K, I, F> GroupInner<K, I, F>
                 },
             }
             self.current_key = Some(key);
-            if self.top_group != self.dropped_group {
-                group.push(elt);
+            if keep {
+                keep = self.buffer_elt(&mut group, elt);
             }
         }
 
-        if self.top_group != self.dropped_group {
+        if keep {
             self.push_next_group(group);
         }
         if first_elt.is_some() {
@@ -193,6 +279,25 @@ impl<K, I, F> GroupInner<K, I, F>
         first_elt
     }
 
+    /// Adds `elt` to the `group` being buffered, unless that would exceed the
+    /// buffer limit. Then, the group is discarded and this returns `false`.
+    fn buffer_elt(&mut self, group: &mut Vec<I::Item>, elt: I::Item) -> bool {
+        if self.stats.buffered + group.len() < self.buffer_limit {
+            group.push(elt);
+            return true;
+        }
+        match self.buffer_policy {
+            BufferLimitPolicy::Panic => {
+                panic!("group buffering exceeded the buffer limit of {}", self.buffer_limit)
+            }
+            BufferLimitPolicy::Error => self.discarded.push(self.top_group),
+            BufferLimitPolicy::Discard => {}
+        }
+        self.stats.discarded_groups += 1;
+        *group = Vec::new();
+        false
+    }
+
     fn push_next_group(&mut self, group: Vec<I::Item>) {
         // When we add a new buffered group, fill up slots between oldest_buffered_group and top_group
         while self.top_group - self.bottom_group > self.buffer.len() {
@@ -203,6 +308,8 @@ impl<K, I, F> GroupInner<K, I, F>
                 self.buffer.push(Vec::new().into_iter());
             }
         }
+        self.stats.buffered += group.len();
+        self.stats.peak_buffered = self.stats.peak_buffered.max(self.stats.buffered);
         self.buffer.push(group.into_iter());
         debug_assert!(self.top_group + 1 - self.bottom_group == self.buffer.len());
     }
@@ -238,7 +345,7 @@ impl<K, I, F> GroupInner<K, I, F>
     /// `client`: Index of group
     ///
     /// **Panics** if no group key is available.
-    fn group_key(&mut self, client: usize) -> K {
+    pub(crate) fn group_key(&mut self, client: usize) -> K {
         // This can only be called after we have just returned the first
         // element of a group.
         // Perform this by simply buffering one more element, grabbing the
@@ -263,12 +370,48 @@ impl<K, I, F> GroupInner<K, I, F>
 impl<K, I, F> GroupInner<K, I, F>
     where I: Iterator,
 {
+    pub(crate) fn new(key: F, iter: I) -> Self {
+        GroupInner {
+            key,
+            iter,
+            current_key: None,
+            current_elt: None,
+            done: false,
+            top_group: 0,
+            oldest_buffered_group: 0,
+            bottom_group: 0,
+            buffer: Vec::new(),
+            dropped_group: !0,
+            buffer_limit: usize::MAX,
+            buffer_policy: BufferLimitPolicy::Panic,
+            discarded: Vec::new(),
+            stats: BufferStats::default(),
+        }
+    }
+
+    pub(crate) fn set_buffer_limit(&mut self, limit: usize, policy: BufferLimitPolicy) {
+        self.buffer_limit = limit;
+        self.buffer_policy = policy;
+    }
+
+    pub(crate) fn buffer_stats(&self) -> BufferStats {
+        self.stats
+    }
+
     /// Called when a group is dropped
-    fn drop_group(&mut self, client: usize) {
+    pub(crate) fn drop_group(&mut self, client: usize) {
         // It's only useful to track the maximal index
         if self.dropped_group == !0 || client > self.dropped_group {
             self.dropped_group = client;
         }
+        // Its buffered elements are no longer needed
+        if client >= self.bottom_group {
+            if let Some(queue) = self.buffer.get_mut(client - self.bottom_group) {
+                self.stats.buffered -= queue.len();
+                *queue = Vec::new().into_iter();
+            }
+        }
+        self.discarded.retain(|&group| group != client);
     }
 }
 
@@ -301,18 +444,7 @@ pub fn new<K, J, F>(iter: J, f: F) -> GroupBy<K, J::IntoIter, F>
           F: FnMut(&J::Item) -> K,
 {
     GroupBy {
-        inner: RefCell::new(GroupInner {
-            key: f,
-            iter: iter.into_iter(),
-            current_key: None,
-            current_elt: None,
-            done: false,
-            top_group: 0,
-            oldest_buffered_group: 0,
-            bottom_group: 0,
-            buffer: Vec::new(),
-            dropped_group: !0,
-        }),
+        inner: RefCell::new(GroupInner::new(f, iter.into_iter())),
         index: Cell::new(0),
     }
 }
@@ -320,6 +452,43 @@ pub fn new<K, J, F>(iter: J, f: F) -> GroupBy<K, J::IntoIter, F>
 impl<K, I, F> GroupBy<K, I, F>
     where I: Iterator,
 {
+    /// Limits the number of elements buffered at once to `limit`, with
+    /// `policy` deciding what happens to a skipped group whose elements do not
+    /// fit.
+    ///
+    /// Without a limit, the elements of every group that is skipped before it
+    /// is fully consumed, and not dropped, are buffered until it is.
+    ///
+    /// ```
+    /// use itertools::{BufferLimitPolicy, Itertools};
+    ///
+    /// let data = vec![0, 0, 0, 0, 1, 1, 2, 2];
+    /// let grouper = data.into_iter()
+    ///     .group_by(|&x| x)
+    ///     .with_buffer_limit(2, BufferLimitPolicy::Error);
+    /// let mut groups = grouper.into_iter().map(|(_, group)| group).collect::<Vec<_>>();
+    ///
+    /// // Each group holds its first element itself. The three other 0s did
+    /// // not fit in the buffer, the other 1 and 2 did.
+    /// assert_eq!(groups[0].try_next(), Ok(Some(0)));
+    /// assert!(groups[0].try_next().is_err());
+    /// assert_eq!(groups[0].try_next(), Ok(None));
+    /// itertools::assert_equal(&mut groups[1], vec![1, 1]);
+    /// itertools::assert_equal(&mut groups[2], vec![2, 2]);
+    ///
+    /// let stats = grouper.buffer_stats();
+    /// assert_eq!((stats.buffered, stats.peak_buffered, stats.discarded_groups), (0, 2, 1));
+    /// ```
+    pub fn with_buffer_limit(self, limit: usize, policy: BufferLimitPolicy) -> Self {
+        self.inner.borrow_mut().set_buffer_limit(limit, policy);
+        self
+    }
+
+    /// Returns statistics about the buffering so far.
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.inner.borrow().buffer_stats()
+    }
+
     /// `client`: Index of group that requests next element
     fn step(&self, client: usize) -> Option<I::Item>
         where F: FnMut(&I::Item) -> K,
@@ -328,6 +497,14 @@ impl<K, I, F> GroupBy<K, I, F>
         self.inner.borrow_mut().step(client)
     }
 
+    /// `client`: Index of group that requests next element
+    fn try_step(&self, client: usize) -> Result<Option<I::Item>, BufferLimitError>
+        where F: FnMut(&I::Item) -> K,
+              K: PartialEq,
+    {
+        self.inner.borrow_mut().try_step(client)
+    }
+
     /// `client`: Index of group
     fn drop_group(&self, client: usize) {
         self.inner.borrow_mut().drop_group(client)
@@ -408,6 +585,22 @@ impl<'a, K, I, F> Drop for Group<'a, K, I, F>
     }
 }
 
+impl<'a, K, I, F> Group<'a, K, I, F>
+    where I: Iterator,
+          I::Item: 'a,
+          F: FnMut(&I::Item) -> K,
+          K: PartialEq,
+{
+    /// Like `next`, but returns an error, once, at the point where the rest
+    /// of the group was discarded under [`BufferLimitPolicy::Error`].
+    pub fn try_next(&mut self) -> Result<Option<I::Item>, BufferLimitError> {
+        if let elt @ Some(..) = self.first.take() {
+            return Ok(elt);
+        }
+        self.parent.try_step(self.index)
+    }
+}
+
 impl<'a, K, I, F> Iterator for Group<'a, K, I, F>
     where I: Iterator,
           I::Item: 'a,
@@ -431,18 +624,7 @@ pub fn new_chunks<J>(iter: J, size: usize) -> IntoChunks<J::IntoIter>
     where J: IntoIterator,
 {
     IntoChunks {
-        inner: RefCell::new(GroupInner {
-            key: ChunkIndex::new(size),
-            iter: iter.into_iter(),
-            current_key: None,
-            current_elt: None,
-            done: false,
-            top_group: 0,
-            oldest_buffered_group: 0,
-            bottom_group: 0,
-            buffer: Vec::new(),
-            dropped_group: !0,
-        }),
+        inner: RefCell::new(GroupInner::new(ChunkIndex::new(size), iter.into_iter())),
         index: Cell::new(0),
     }
 }
@@ -475,11 +657,31 @@ pub struct IntoChunks<I>
 impl<I> IntoChunks<I>
     where I: Iterator,
 {
+    /// Limits the number of elements buffered at once to `limit`, with
+    /// `policy` deciding what happens to a skipped chunk whose elements do not
+    /// fit.
+    ///
+    /// See [`GroupBy::with_buffer_limit`] for more information.
+    pub fn with_buffer_limit(self, limit: usize, policy: BufferLimitPolicy) -> Self {
+        self.inner.borrow_mut().set_buffer_limit(limit, policy);
+        self
+    }
+
+    /// Returns statistics about the buffering so far.
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.inner.borrow().buffer_stats()
+    }
+
     /// `client`: Index of chunk that requests next element
     fn step(&self, client: usize) -> Option<I::Item> {
         self.inner.borrow_mut().step(client)
     }
 
+    /// `client`: Index of chunk that requests next element
+    fn try_step(&self, client: usize) -> Result<Option<I::Item>, BufferLimitError> {
+        self.inner.borrow_mut().try_step(client)
+    }
+
     /// `client`: Index of chunk
     fn drop_group(&self, client: usize) {
         self.inner.borrow_mut().drop_group(client)
@@ -556,6 +758,20 @@ impl<'a, I> Drop for Chunk<'a, I>
     }
 }
 
+impl<'a, I> Chunk<'a, I>
+    where I: Iterator,
+          I::Item: 'a,
+{
+    /// Like `next`, but returns an error, once, at the point where the rest
+    /// of the chunk was discarded under [`BufferLimitPolicy::Error`].
+    pub fn try_next(&mut self) -> Result<Option<I::Item>, BufferLimitError> {
+        if let elt @ Some(..) = self.first.take() {
+            return Ok(elt);
+        }
+        self.parent.try_step(self.index)
+    }
+}
+
 impl<'a, I> Iterator for Chunk<'a, I>
     where I: Iterator,
           I::Item: 'a,
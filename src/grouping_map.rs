@@ -1,11 +1,16 @@
-#![cfg(feature = "use_std")]
+#![cfg(feature = "use_alloc")]
 
 use crate::MinMaxResult;
-use std::collections::HashMap;
+use crate::aggregations::{Aggregation, RunningMean};
+#[cfg(feature = "use_std")]
+use crate::map_kind::HashMapKind;
+#[cfg(feature = "use_std")]
+use crate::map_kind::OrderableMapKind;
+use crate::map_kind::{MapKind, NestableMapKind, NestedMapKind};
 use std::cmp::Ordering;
-use std::hash::Hash;
 use std::iter::Iterator;
 use std::ops::{Add, Mul};
+use alloc::vec::Vec;
 
 /// A wrapper to allow for an easy [`into_grouping_map_by`](../trait.Itertools.html#method.into_grouping_map_by)
 #[derive(Clone, Debug)]
@@ -19,7 +24,6 @@ impl<I, F> MapForGrouping<I, F> {
 
 impl<K, V, I, F> Iterator for MapForGrouping<I, F>
     where I: Iterator<Item = V>,
-          K: Hash + Eq,
           F: FnMut(&V) -> K,
 {
     type Item = (K, V);
@@ -28,42 +32,166 @@ impl<K, V, I, F> Iterator for MapForGrouping<I, F>
     }
 }
 
+/// A wrapper to key the elements of a `GroupingMap` again, see [`then_by`](GroupingMap::then_by)
+#[derive(Clone, Debug)]
+pub struct MapForThenBy<I, F>(I, F);
+
+impl<K1, K2, V, I, F> Iterator for MapForThenBy<I, F>
+    where I: Iterator<Item = (K1, V)>,
+          F: FnMut(&V) -> K2,
+{
+    type Item = ((K1, K2), V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let key_mapper = &mut self.1;
+        self.0.next().map(|(key, val)| ((key, key_mapper(&val)), val))
+    }
+}
+
 /// Creates a new `GroupingMap` from `iter`
+#[cfg(feature = "use_std")]
 pub fn new<I, K, V>(iter: I) -> GroupingMap<I>
     where I: Iterator<Item = (K, V)>,
-          K: Hash + Eq,
 {
-    GroupingMap { iter }
+    new_in(iter, HashMapKind::default())
+}
+
+/// Creates a new `GroupingMap` from `iter`, collecting in maps of the given kind
+pub fn new_in<I, K, V, M>(iter: I, kind: M) -> GroupingMap<I, M>
+    where I: Iterator<Item = (K, V)>,
+{
+    GroupingMap { iter, kind }
 }
 
 /// `GroupingMapBy` is an intermediate struct for efficient group-and-fold operations.
 /// 
 /// See [`GroupingMap`](./struct.GroupingMap.html) for more informations.
+#[cfg(feature = "use_std")]
+#[must_use = "GroupingMapBy is lazy and do nothing unless consumed"]
+pub type GroupingMapBy<I, F, M = HashMapKind> = GroupingMap<MapForGrouping<I, F>, M>;
+
+/// `GroupingMapBy` is an intermediate struct for efficient group-and-fold operations.
+///
+/// See [`GroupingMap`](./struct.GroupingMap.html) for more informations.
+// Without `std` there is no default map kind.
+#[cfg(not(feature = "use_std"))]
 #[must_use = "GroupingMapBy is lazy and do nothing unless consumed"]
-pub type GroupingMapBy<I, F> = GroupingMap<MapForGrouping<I, F>>;
+pub type GroupingMapBy<I, F, M> = GroupingMap<MapForGrouping<I, F>, M>;
 
 /// `GroupingMap` is an intermediate struct for efficient group-and-fold operations.
 /// It groups elements by their key and at the same time fold each group
 /// using some aggregating operation.
 /// 
-/// No method on this struct performs temporary allocations.
+/// No method on this struct performs temporary allocations, other than the
+/// clones of elements that some aggregations of
+/// [`aggregate_many`](GroupingMap::aggregate_many) make.
+///
+/// The results are collected in maps of kind `M`: `HashMap`s by default, or
+/// any other [`MapKind`](crate::traits::MapKind), such as `BTreeMap`s to get
+/// the keys in order.
+#[cfg(feature = "use_std")]
+#[derive(Clone, Debug)]
+#[must_use = "GroupingMap is lazy and do nothing unless consumed"]
+pub struct GroupingMap<I, M = HashMapKind> {
+    iter: I,
+    kind: M,
+}
+
+/// `GroupingMap` is an intermediate struct for efficient group-and-fold operations.
+/// It groups elements by their key and at the same time fold each group
+/// using some aggregating operation.
+///
+/// No method on this struct performs temporary allocations, other than the
+/// clones of elements that some aggregations of
+/// [`aggregate_many`](GroupingMap::aggregate_many) make.
+///
+/// The results are collected in maps of kind `M`, any
+/// [`MapKind`](crate::traits::MapKind), such as `BTreeMap`s to get the keys in
+/// order.
+// Without `std` there is no default map kind.
+#[cfg(not(feature = "use_std"))]
 #[derive(Clone, Debug)]
 #[must_use = "GroupingMap is lazy and do nothing unless consumed"]
-pub struct GroupingMap<I> {
+pub struct GroupingMap<I, M> {
     iter: I,
+    kind: M,
 }
 
-impl<I, K, V> GroupingMap<I>
+impl<I, K, V, M> GroupingMap<I, M>
     where I: Iterator<Item = (K, V)>,
-          K: Hash + Eq,
 {
+    /// Groups the elements of each group again, by the key `key_mapper` gives them,
+    /// so that the operation that follows is applied to these inner groups.
+    ///
+    /// Its map then associates the key of each group with a map, of the same kind,
+    /// associating the key of each inner group with its result. Operations that pass
+    /// keys to a closure, like [`aggregate`](Self::aggregate), pass the pair of both
+    /// keys. Calling `then_by` again adds another level of maps.
+    ///
+    /// Everything is still done in a single pass over the elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sales = vec![("north", "apple", 3), ("south", "apple", 5), ("north", "pear", 2),
+    ///                  ("north", "apple", 4), ("south", "plum", 1)];
+    /// let lookup = sales.into_iter()
+    ///     .into_grouping_map_by(|&(region, _, _)| region)
+    ///     .then_by(|&(_, product, _)| product)
+    ///     .fold(0, |acc, _key, (_, _, n)| acc + n);
+    ///
+    /// assert_eq!(lookup["north"]["apple"], 3 + 4);
+    /// assert_eq!(lookup["north"]["pear"], 2);
+    /// assert_eq!(lookup["south"]["apple"], 5);
+    /// assert_eq!(lookup["south"]["plum"], 1);
+    /// assert_eq!(lookup.len(), 2);
+    /// assert_eq!(lookup["north"].len(), 2);
+    /// ```
+    pub fn then_by<F, K2>(self, key_mapper: F) -> GroupingMap<MapForThenBy<I, F>, NestedMapKind<M, M::Innermost>>
+        where F: FnMut(&V) -> K2,
+              M: NestableMapKind,
+    {
+        let inner = self.kind.innermost();
+        GroupingMap {
+            iter: MapForThenBy(self.iter, key_mapper),
+            kind: NestedMapKind::new(self.kind, inner),
+        }
+    }
+
+    /// Collects the results in [`OrderedMap`](crate::OrderedMap)s instead,
+    /// which keep the keys in the order they are first seen, so that the
+    /// groups always come out in the same order.
+    ///
+    /// The maps keep their levels, from [`then_by`](Self::then_by) called
+    /// before or after, and their hasher.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![5, 2, 8, 3, 1, 4, 7];
+    /// let lookup = data.into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .ordered()
+    ///     .sum();
+    ///
+    /// itertools::assert_equal(lookup, vec![(2, 5 + 2 + 8), (0, 3), (1, 1 + 4 + 7)]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    pub fn ordered(self) -> GroupingMap<I, M::Ordered>
+        where M: OrderableMapKind,
+    {
+        GroupingMap {
+            iter: self.iter,
+            kind: self.kind.ordered(),
+        }
+    }
+
     /// This is the generic way to perform any operation on a `GroupingMap`.
     /// It's suggested to use this method only to implement custom operations
     /// when the already provided ones are not enough.
     /// 
     /// Groups elements from the `GroupingMap` source by key and applies `operation` to the elements
     /// of each group sequentially, passing the previously accumulated value, a reference to the key
-    /// and the current element as arguments, and stores the results in an `HashMap`.
+    /// and the current element as arguments, and stores the results in a map.
     ///
     /// The `operation` function is invoked on each element with the following parameters:
     ///  - the current value of the accumulator of the group if there is currently one;
@@ -73,7 +201,7 @@ impl<I, K, V> GroupingMap<I>
     /// If `operation` returns `Some(element)` then the accumulator is updated with `element`,
     /// otherwise the previous accumulation is discarded.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of aggregation of
+    /// Return a map associating the key of each group with the result of aggregation of
     /// that group's elements. If the aggregation of the last element of a group discards the
     /// accumulator then there won't be an entry associated to that group's key.
     /// 
@@ -97,16 +225,17 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&3], 7);
     /// assert_eq!(lookup.len(), 3);      // The final keys are only 0, 1 and 2
     /// ```
-    pub fn aggregate<FO, R>(self, mut operation: FO) -> HashMap<K, R>
+    pub fn aggregate<FO, R>(self, mut operation: FO) -> M::Map
         where FO: FnMut(Option<R>, &K, V) -> Option<R>,
+              M: MapKind<K, R>,
     {
-        let mut destination_map = HashMap::new();
+        let mut destination_map = self.kind.new_map();
 
         for (key, val) in self.iter {
-            let acc = destination_map.remove(&key);
-            if let Some(op_res) = operation(acc, &key, val) {
-                destination_map.insert(key, op_res);
-            }
+            self.kind.update(&mut destination_map, key, |key, acc| {
+                let acc = operation(acc, &key, val);
+                (key, acc)
+            });
         }
 
         destination_map
@@ -123,7 +252,7 @@ impl<I, K, V> GroupingMap<I>
     ///  - a reference to the key of the group this element belongs to;
     ///  - the element from the source being accumulated.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of folding that group's elements.
+    /// Return a map associating the key of each group with the result of folding that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -137,9 +266,10 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 2 + 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn fold<FO, R>(self, init: R, mut operation: FO) -> HashMap<K, R>
+    pub fn fold<FO, R>(self, init: R, mut operation: FO) -> M::Map
         where R: Clone,
               FO: FnMut(R, &K, V) -> R,
+              M: MapKind<K, R>,
     {
         self.aggregate(|acc, key, val| {
             let acc = acc.unwrap_or_else(|| init.clone());
@@ -158,7 +288,7 @@ impl<I, K, V> GroupingMap<I>
     ///  - a reference to the key of the group this element belongs to;
     ///  - the element from the source being accumulated.
     ///
-    /// Return a `HashMap` associating the key of each group with the result of folding that group's elements.
+    /// Return a map associating the key of each group with the result of folding that group's elements.
     /// 
     /// [`fold`]: #tymethod.fold
     /// 
@@ -174,8 +304,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 2 + 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn fold_first<FO>(self, mut operation: FO) -> HashMap<K, V>
+    pub fn fold_first<FO>(self, mut operation: FO) -> M::Map
         where FO: FnMut(V, &K, V) -> V,
+              M: MapKind<K, V>,
     {
         self.aggregate(|acc, key, val| {
             Some(match acc {
@@ -188,7 +319,7 @@ impl<I, K, V> GroupingMap<I>
     /// Groups elements from the `GroupingMap` source by key and collects the elements of each group in
     /// an instance of `C`. The iteration order is preserved when inserting elements. 
     /// 
-    /// Return a `HashMap` associating the key of each group with the collection containing that group's elements.
+    /// Return a map associating the key of each group with the collection containing that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -203,13 +334,14 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], vec![2, 5].into_iter().collect::<HashSet<_>>());
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn collect<C>(self) -> HashMap<K, C>
+    pub fn collect<C>(self) -> M::Map
         where C: Default + Extend<V>,
+              M: MapKind<K, C>,
     {
-        let mut destination_map = HashMap::new();
+        let mut destination_map = self.kind.new_map();
 
         for (key, val) in self.iter {
-            destination_map.entry(key).or_insert_with(C::default).extend(Some(val));
+            self.kind.entry_or_insert_with(&mut destination_map, key, C::default).extend(Some(val));
         }
 
         destination_map
@@ -219,7 +351,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally maximum, the last element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -233,8 +365,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max(self) -> HashMap<K, V>
+    pub fn max(self) -> M::Map
         where V: Ord,
+              M: MapKind<K, V>,
     {
         self.max_by(|_, v1, v2| V::cmp(v1, v2))
     }
@@ -244,7 +377,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally maximum, the last element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -258,8 +391,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max_by<F>(self, mut compare: F) -> HashMap<K, V>
+    pub fn max_by<F>(self, mut compare: F) -> M::Map
         where F: FnMut(&K, &V, &V) -> Ordering,
+              M: MapKind<K, V>,
     {
         self.fold_first(|acc, key, val| match compare(key, &acc, &val) {
             Ordering::Less | Ordering::Equal => val,
@@ -272,7 +406,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally maximum, the last element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the maximum of that group's elements.
+    /// Returns a map associating the key of each group with the maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -286,9 +420,10 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn max_by_key<F, CK>(self, mut f: F) -> HashMap<K, V>
+    pub fn max_by_key<F, CK>(self, mut f: F) -> M::Map
         where F: FnMut(&K, &V) -> CK,
               CK: Ord,
+              M: MapKind<K, V>,
     {
         self.max_by(|key, v1, v2| f(key, &v1).cmp(&f(key, &v2)))
     }
@@ -297,7 +432,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally minimum, the first element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -311,8 +446,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 5);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min(self) -> HashMap<K, V>
+    pub fn min(self) -> M::Map
         where V: Ord,
+              M: MapKind<K, V>,
     {
         self.min_by(|_, v1, v2| V::cmp(v1, v2))
     }
@@ -322,7 +458,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally minimum, the first element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -336,8 +472,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min_by<F>(self, mut compare: F) -> HashMap<K, V>
+    pub fn min_by<F>(self, mut compare: F) -> M::Map
         where F: FnMut(&K, &V, &V) -> Ordering,
+              M: MapKind<K, V>,
     {
         self.fold_first(|acc, key, val| match compare(key, &acc, &val) {
             Ordering::Less | Ordering::Equal => acc,
@@ -350,7 +487,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// If several elements are equally minimum, the first element is picked.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -364,9 +501,10 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn min_by_key<F, CK>(self, mut f: F) -> HashMap<K, V>
+    pub fn min_by_key<F, CK>(self, mut f: F) -> M::Map
         where F: FnMut(&K, &V) -> CK,
               CK: Ord,
+              M: MapKind<K, V>,
     {
         self.min_by(|key, v1, v2| f(key, &v1).cmp(&f(key, &v2)))
     }
@@ -383,7 +521,7 @@ impl<I, K, V> GroupingMap<I>
     /// - It never produces a `MinMaxResult::NoElements`
     /// - It doesn't have any speedup
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -398,8 +536,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax(self) -> HashMap<K, MinMaxResult<V>>
+    pub fn minmax(self) -> M::Map
         where V: Ord,
+              M: MapKind<K, MinMaxResult<V>>,
     {
         self.minmax_by(|_, v1, v2| V::cmp(v1, v2))
     }
@@ -412,7 +551,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// It has the same differences from the non-grouping version as `minmax`.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -427,8 +566,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax_by<F>(self, mut compare: F) -> HashMap<K, MinMaxResult<V>>
+    pub fn minmax_by<F>(self, mut compare: F) -> M::Map
         where F: FnMut(&K, &V, &V) -> Ordering,
+              M: MapKind<K, MinMaxResult<V>>,
     {
         self.aggregate(|acc, key, val| {
             Some(match acc {
@@ -462,7 +602,7 @@ impl<I, K, V> GroupingMap<I>
     /// 
     /// It has the same differences from the non-grouping version as `minmax`.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the minimum and maximum of that group's elements.
+    /// Returns a map associating the key of each group with the minimum and maximum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -477,9 +617,10 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], OneElement(5));
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn minmax_by_key<F, CK>(self, mut f: F) -> HashMap<K, MinMaxResult<V>>
+    pub fn minmax_by_key<F, CK>(self, mut f: F) -> M::Map
         where F: FnMut(&K, &V) -> CK,
               CK: Ord,
+              M: MapKind<K, MinMaxResult<V>>,
     {
         self.minmax_by(|key, v1, v2| f(key, &v1).cmp(&f(key, &v2)))
     }
@@ -489,7 +630,7 @@ impl<I, K, V> GroupingMap<I>
     /// This is just a shorthand for `self.fold_first(|acc, _, val| acc + val)`.
     /// It is more limited than `Iterator::sum` since it doesn't use the `Sum` trait.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the sum of that group's elements.
+    /// Returns a map associating the key of each group with the sum of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -503,8 +644,9 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 5 + 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn sum(self) -> HashMap<K, V>
-        where V: Add<V, Output = V>
+    pub fn sum(self) -> M::Map
+        where V: Add<V, Output = V>,
+              M: MapKind<K, V>,
     {
         self.fold_first(|acc, _, val| acc + val)
     }
@@ -514,7 +656,7 @@ impl<I, K, V> GroupingMap<I>
     /// This is just a shorthand for `self.fold_first(|acc, _, val| acc * val)`.
     /// It is more limited than `Iterator::product` since it doesn't use the `Product` trait.
     /// 
-    /// Returns a `HashMap` associating the key of each group with the product of that group's elements.
+    /// Returns a map associating the key of each group with the product of that group's elements.
     /// 
     /// ```
     /// use itertools::Itertools;
@@ -528,9 +670,264 @@ impl<I, K, V> GroupingMap<I>
     /// assert_eq!(lookup[&2], 5 * 8);
     /// assert_eq!(lookup.len(), 3);
     /// ```
-    pub fn product(self) -> HashMap<K, V>
+    pub fn product(self) -> M::Map
         where V: Mul<V, Output = V>,
+              M: MapKind<K, V>,
     {
         self.fold_first(|acc, _, val| acc * val)
     }
+
+    /// Groups elements from the `GroupingMap` source by key and counts them.
+    ///
+    /// Returns a map associating the key of each group with the number of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .count();
+    ///
+    /// assert_eq!(lookup[&0], 3);
+    /// assert_eq!(lookup[&1], 3);
+    /// assert_eq!(lookup[&2], 2);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn count(self) -> M::Map
+        where M: MapKind<K, usize>,
+    {
+        self.fold(0, |acc, _, _| acc + 1)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the first element of each
+    /// group.
+    ///
+    /// Returns a map associating the key of each group with the first of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .first();
+    ///
+    /// assert_eq!(lookup[&0], 3);
+    /// assert_eq!(lookup[&1], 1);
+    /// assert_eq!(lookup[&2], 5);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn first(self) -> M::Map
+        where M: MapKind<K, V>,
+    {
+        self.fold_first(|acc, _, _| acc)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the last element of each
+    /// group.
+    ///
+    /// Returns a map associating the key of each group with the last of that group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .last();
+    ///
+    /// assert_eq!(lookup[&0], 12);
+    /// assert_eq!(lookup[&1], 7);
+    /// assert_eq!(lookup[&2], 8);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn last(self) -> M::Map
+        where M: MapKind<K, V>,
+    {
+        self.fold_first(|_, _, val| val)
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` smallest elements
+    /// of each group, in ascending order.
+    ///
+    /// If several elements are equal, the first ones are picked. Each group only ever holds up
+    /// to `k` elements.
+    ///
+    /// Returns a map associating the key of each group with a `Vec` of the smallest of that
+    /// group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 9, 8, 7, 5, 4, 3, 1].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_smallest(2);
+    ///
+    /// assert_eq!(lookup[&0], vec![3, 9]);
+    /// assert_eq!(lookup[&1], vec![1, 4]);
+    /// assert_eq!(lookup[&2], vec![5, 8]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_smallest(self, k: usize) -> M::Map
+        where V: Ord,
+              M: MapKind<K, Vec<V>>,
+    {
+        self.aggregate(|acc, _, val| {
+            let mut group = acc.unwrap_or_default();
+            insert_bounded(&mut group, k, val, V::cmp);
+            Some(group)
+        })
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and keeps the `k` largest elements
+    /// of each group, in descending order.
+    ///
+    /// If several elements are equal, the first ones are picked. Each group only ever holds up
+    /// to `k` elements.
+    ///
+    /// Returns a map associating the key of each group with a `Vec` of the largest of that
+    /// group's elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .k_largest(2);
+    ///
+    /// assert_eq!(lookup[&0], vec![12, 9]);
+    /// assert_eq!(lookup[&1], vec![7, 4]);
+    /// assert_eq!(lookup[&2], vec![8, 5]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn k_largest(self, k: usize) -> M::Map
+        where V: Ord,
+              M: MapKind<K, Vec<V>>,
+    {
+        self.aggregate(|acc, _, val| {
+            let mut group = acc.unwrap_or_default();
+            insert_bounded(&mut group, k, val, |a, b| b.cmp(a));
+            Some(group)
+        })
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and averages them.
+    ///
+    /// The elements are converted to `f64`; other numeric types, like `i64`, can be converted
+    /// with `as` beforehand.
+    ///
+    /// Returns a map associating the key of each group with a [`RunningMean`] of that group's
+    /// elements, which knows their mean, sum and count.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .mean();
+    ///
+    /// assert_eq!(lookup[&0].mean(), 8.0);
+    /// assert_eq!(lookup[&1].mean(), 4.0);
+    /// assert_eq!(lookup[&2].mean(), 6.5);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn mean(self) -> M::Map
+        where V: Into<f64>,
+              M: MapKind<K, RunningMean>,
+    {
+        self.aggregate(|acc, _, val| {
+            Some(match acc {
+                Some(acc) => acc.push(val.into()),
+                None => RunningMean::new(val.into()),
+            })
+        })
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and collects the elements of each
+    /// group in a `Vec`, sorted in ascending order.
+    ///
+    /// The groups are sorted with [`slice::sort_unstable`], so equal elements may be reordered.
+    ///
+    /// Returns a map associating the key of each group with the sorted elements of that group.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let lookup = vec![12, 1, 9, 4, 3, 8, 5, 7].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .sorted_collect();
+    ///
+    /// assert_eq!(lookup[&0], vec![3, 9, 12]);
+    /// assert_eq!(lookup[&1], vec![1, 4, 7]);
+    /// assert_eq!(lookup[&2], vec![5, 8]);
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn sorted_collect(self) -> M::Map
+        where V: Ord,
+              M: MapKind<K, Vec<V>>,
+    {
+        let mut destination_map = self.kind.new_map();
+
+        for (key, val) in self.iter {
+            self.kind.entry_or_insert_with(&mut destination_map, key, Vec::new).push(val);
+        }
+
+        self.kind.for_each_value_mut(&mut destination_map, |group| group.sort_unstable());
+        destination_map
+    }
+
+    /// Groups elements from the `GroupingMap` source by key and computes several aggregations
+    /// of each group in a single pass.
+    ///
+    /// `aggregations` is an [`Aggregation`], usually a tuple of the named ones in
+    /// [`itertools::aggregations`](crate::aggregations).
+    ///
+    /// Returns a map associating the key of each group with the results of the aggregations of
+    /// that group's elements, as a tuple.
+    ///
+    /// The aggregations see each element by reference, so those that combine elements by value,
+    /// like [`Sum`](crate::aggregations::Sum), clone each of them. Those that keep elements, like
+    /// [`Max`](crate::aggregations::Max), clone them into the value they replace, reusing its
+    /// buffer.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use itertools::aggregations::{Count, First, Min, Max};
+    ///
+    /// let lookup = vec![1, 3, 4, 5, 7, 8, 9, 12].into_iter()
+    ///     .into_grouping_map_by(|&n| n % 3)
+    ///     .aggregate_many((Count, First, Min, Max));
+    ///
+    /// assert_eq!(lookup[&0], (3, 3, 3, 12));
+    /// assert_eq!(lookup[&1], (3, 1, 1, 7));
+    /// assert_eq!(lookup[&2], (2, 5, 5, 8));
+    /// assert_eq!(lookup.len(), 3);
+    /// ```
+    pub fn aggregate_many<A>(self, mut aggregations: A) -> M::Map
+        where A: Aggregation<K, V>,
+              M: MapKind<K, A::Output>,
+    {
+        self.aggregate(|acc, key, val| {
+            Some(match acc {
+                Some(acc) => aggregations.update(acc, key, &val),
+                None => aggregations.start(key, &val),
+            })
+        })
+    }
+}
+
+/// Inserts `val` into `group`, which is sorted by `compare`, keeping only its first `k`
+/// elements. Equal elements stay in insertion order.
+fn insert_bounded<V, F>(group: &mut Vec<V>, k: usize, val: V, mut compare: F)
+    where F: FnMut(&V, &V) -> Ordering,
+{
+    if group.len() == k {
+        match group.last() {
+            Some(last) if compare(&val, last) == Ordering::Less => {
+                group.pop();
+            }
+            _ => return,
+        }
+    }
+
+    let index = group.partition_point(|x| compare(x, &val) != Ordering::Greater);
+    group.insert(index, val);
 }
@@ -0,0 +1,453 @@
+use alloc::vec::Vec;
+
+use super::combinations::checked_binomial;
+
+/// An iterator over the `k`-length combinations of the indices `0..n`, as
+/// `S`: a `Vec<usize>` or a `[usize; K]`.
+///
+/// See [`index_combinations`] and [`index_array_combinations`] for more
+/// information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IndexCombinations<S = Vec<usize>> {
+    n: usize,
+    // The next combinations to yield from the front and from the back.
+    front: S,
+    back: S,
+    done: bool,
+}
+
+/// An iterator over the `k`-length permutations of the indices `0..n`, as
+/// `S`: a `Vec<usize>` or a `[usize; K]`.
+///
+/// See [`index_permutations`] and [`index_array_permutations`] for more
+/// information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IndexPermutations<S = Vec<usize>> {
+    n: usize,
+    // The next permutations to yield from the front and from the back.
+    front: S,
+    back: S,
+    done: bool,
+}
+
+/// An iterator over the `k`-length combinations of the indices `0..n`, with
+/// replacement, as `S`: a `Vec<usize>` or a `[usize; K]`.
+///
+/// See [`index_combinations_with_replacement`] and
+/// [`index_array_combinations_with_replacement`] for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IndexCombinationsWithReplacement<S = Vec<usize>> {
+    n: usize,
+    // The next combinations to yield from the front and from the back.
+    front: S,
+    back: S,
+    done: bool,
+}
+
+/// Return an iterator over the `k`-length combinations of the indices `0..n`,
+/// in lexicographic order.
+///
+/// Iterator element type is `Vec<usize>`: the indices of each combination, in
+/// increasing order. This is like
+/// [`(0..n).combinations(k)`](crate::Itertools::combinations), but no items are
+/// buffered, and the iterator is double ended to walk the combinations in
+/// reverse lexicographic order too.
+///
+/// ```
+/// use itertools::index_combinations;
+///
+/// itertools::assert_equal(index_combinations(4, 2), vec![
+///     vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3],
+/// ]);
+/// itertools::assert_equal(index_combinations(3, 2).rev(), vec![
+///     vec![1, 2], vec![0, 2], vec![0, 1],
+/// ]);
+/// ```
+pub fn index_combinations(n: usize, k: usize) -> IndexCombinations {
+    IndexCombinations::new(n, alloc::vec![0; k], alloc::vec![0; k])
+}
+
+/// Return an iterator over the `K`-length combinations of the indices `0..n`,
+/// as arrays, in lexicographic order.
+///
+/// Iterator element type is `[usize; K]`. This is like [`index_combinations`],
+/// but no combination is allocated.
+///
+/// ```
+/// use itertools::index_array_combinations;
+///
+/// itertools::assert_equal(index_array_combinations(3), vec![[0, 1], [0, 2], [1, 2]]);
+/// itertools::assert_equal(index_array_combinations::<3>(3).rev(), vec![[0, 1, 2]]);
+/// ```
+pub fn index_array_combinations<const K: usize>(n: usize) -> IndexCombinations<[usize; K]> {
+    IndexCombinations::new(n, [0; K], [0; K])
+}
+
+/// Return an iterator over the `k`-length permutations of the indices `0..n`,
+/// in lexicographic order.
+///
+/// Iterator element type is `Vec<usize>`: the indices of each permutation. This
+/// is like [`(0..n).permutations(k)`](crate::Itertools::permutations), but no
+/// items are buffered, and the iterator is double ended to walk the
+/// permutations in reverse lexicographic order too.
+///
+/// ```
+/// use itertools::index_permutations;
+///
+/// itertools::assert_equal(index_permutations(3, 2), vec![
+///     vec![0, 1], vec![0, 2], vec![1, 0], vec![1, 2], vec![2, 0], vec![2, 1],
+/// ]);
+/// itertools::assert_equal(index_permutations(3, 3).rev().take(2), vec![
+///     vec![2, 1, 0], vec![2, 0, 1],
+/// ]);
+/// ```
+pub fn index_permutations(n: usize, k: usize) -> IndexPermutations {
+    IndexPermutations::new(n, alloc::vec![0; k], alloc::vec![0; k])
+}
+
+/// Return an iterator over the `K`-length permutations of the indices `0..n`,
+/// as arrays, in lexicographic order.
+///
+/// Iterator element type is `[usize; K]`. This is like [`index_permutations`],
+/// but no permutation is allocated.
+///
+/// ```
+/// use itertools::index_array_permutations;
+///
+/// itertools::assert_equal(index_array_permutations(2), vec![[0, 1], [1, 0]]);
+/// itertools::assert_equal(index_array_permutations::<1>(3).rev(), vec![[2], [1], [0]]);
+/// ```
+pub fn index_array_permutations<const K: usize>(n: usize) -> IndexPermutations<[usize; K]> {
+    IndexPermutations::new(n, [0; K], [0; K])
+}
+
+/// Return an iterator over the `k`-length combinations of the indices `0..n`
+/// with replacement, in lexicographic order.
+///
+/// Iterator element type is `Vec<usize>`: the indices of each combination, in
+/// nondecreasing order. This is like
+/// [`(0..n).combinations_with_replacement(k)`](crate::Itertools::combinations_with_replacement),
+/// but no items are buffered, and the iterator is double ended to walk the
+/// combinations in reverse lexicographic order too.
+///
+/// ```
+/// use itertools::index_combinations_with_replacement;
+///
+/// itertools::assert_equal(index_combinations_with_replacement(3, 2), vec![
+///     vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 1], vec![1, 2], vec![2, 2],
+/// ]);
+/// itertools::assert_equal(index_combinations_with_replacement(2, 3).rev(), vec![
+///     vec![1, 1, 1], vec![0, 1, 1], vec![0, 0, 1], vec![0, 0, 0],
+/// ]);
+/// ```
+pub fn index_combinations_with_replacement(n: usize, k: usize) -> IndexCombinationsWithReplacement {
+    IndexCombinationsWithReplacement::new(n, alloc::vec![0; k], alloc::vec![0; k])
+}
+
+/// Return an iterator over the `K`-length combinations of the indices `0..n`
+/// with replacement, as arrays, in lexicographic order.
+///
+/// Iterator element type is `[usize; K]`. This is like
+/// [`index_combinations_with_replacement`], but no combination is allocated.
+///
+/// ```
+/// use itertools::index_array_combinations_with_replacement;
+///
+/// itertools::assert_equal(index_array_combinations_with_replacement(2), vec![
+///     [0, 0], [0, 1], [1, 1],
+/// ]);
+/// ```
+pub fn index_array_combinations_with_replacement<const K: usize>(n: usize)
+    -> IndexCombinationsWithReplacement<[usize; K]>
+{
+    IndexCombinationsWithReplacement::new(n, [0; K], [0; K])
+}
+
+impl<S> IndexCombinations<S>
+    where S: AsRef<[usize]> + AsMut<[usize]>,
+{
+    /// Starts from the first and the last combination, of the length of `front`
+    /// and `back`.
+    fn new(n: usize, mut front: S, mut back: S) -> Self {
+        let k = front.as_ref().len();
+        for (i, index) in front.as_mut().iter_mut().enumerate() {
+            *index = i;
+        }
+        for (i, index) in back.as_mut().iter_mut().enumerate() {
+            *index = n.saturating_sub(k) + i;
+        }
+        IndexCombinations { n, front, back, done: k > n }
+    }
+
+    /// Moves `front` to the next combination, which must exist.
+    fn increment(&mut self) {
+        let indices = self.front.as_mut();
+        let (n, k) = (self.n, indices.len());
+
+        // Scan from the end, looking for an index that can be increased
+        let mut i = k - 1;
+        while indices[i] == i + n - k {
+            i -= 1;
+        }
+
+        // Increment index, and reset the ones to its right
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+
+    /// Moves `back` to the previous combination, which must exist.
+    fn decrement(&mut self) {
+        let indices = self.back.as_mut();
+        let (n, k) = (self.n, indices.len());
+
+        // Scan from the end, looking for an index that can be decreased
+        let mut i = k - 1;
+        while i > 0 && indices[i] == indices[i - 1] + 1 {
+            i -= 1;
+        }
+
+        // Decrement index, and move the ones to its right as far up as they go
+        indices[i] -= 1;
+        for (j, index) in indices.iter_mut().enumerate().skip(i + 1) {
+            *index = j + n - k;
+        }
+    }
+
+    /// Counts the combinations after `indices`, or `None` on overflow.
+    ///
+    /// Each index `c` at position `i` is followed by all the choices of the
+    /// remaining `k - i` indices above it, `binomial(n - 1 - c, k - i)`.
+    fn after(&self, indices: &[usize]) -> Option<usize> {
+        let k = indices.len();
+        indices.iter().enumerate().try_fold(0usize, |sum, (i, &index)| {
+            sum.checked_add(checked_binomial(self.n - 1 - index, k - i)?)
+        })
+    }
+}
+
+impl<S> IndexPermutations<S>
+    where S: AsRef<[usize]> + AsMut<[usize]>,
+{
+    /// Starts from the first and the last permutation, of the length of `front`
+    /// and `back`.
+    fn new(n: usize, mut front: S, mut back: S) -> Self {
+        let k = front.as_ref().len();
+        let done = k > n;
+        if !done {
+            take_unused(front.as_mut(), 0, 0..n);
+            take_unused(back.as_mut(), 0, (0..n).rev());
+        }
+        IndexPermutations { n, front, back, done }
+    }
+
+    /// Moves `front` to the next permutation, which must exist.
+    fn increment(&mut self) {
+        let n = self.n;
+        let indices = self.front.as_mut();
+
+        // Scan from the end, looking for an index that can take a larger unused one
+        for i in (0..indices.len()).rev() {
+            let used = &indices[..i];
+            if let Some(next) = (indices[i] + 1..n).find(|index| !used.contains(index)) {
+                indices[i] = next;
+                // Reset the ones to its right to the smallest unused indices
+                take_unused(indices, i + 1, 0..n);
+                return;
+            }
+        }
+    }
+
+    /// Moves `back` to the previous permutation, which must exist.
+    fn decrement(&mut self) {
+        let n = self.n;
+        let indices = self.back.as_mut();
+
+        // Scan from the end, looking for an index that can take a smaller unused one
+        for i in (0..indices.len()).rev() {
+            let used = &indices[..i];
+            if let Some(prev) = (0..indices[i]).rev().find(|index| !used.contains(index)) {
+                indices[i] = prev;
+                // Move the ones to its right to the largest unused indices
+                take_unused(indices, i + 1, (0..n).rev());
+                return;
+            }
+        }
+    }
+
+    /// Counts the permutations after `indices`, or `None` on overflow.
+    ///
+    /// Each larger index still unused at position `i` would be followed by all
+    /// the arrangements of the remaining `k - 1 - i` positions.
+    fn after(&self, indices: &[usize]) -> Option<usize> {
+        let (n, k) = (self.n, indices.len());
+        let mut sum: usize = 0;
+        for (i, &index) in indices.iter().enumerate() {
+            let used_larger = indices[..i].iter().filter(|&&other| other > index).count();
+            let larger = n - 1 - index - used_larger;
+            if larger > 0 {
+                let arrangements = (n - k + 1..n - i).try_fold(1usize, |acc, m| acc.checked_mul(m))?;
+                sum = sum.checked_add(larger.checked_mul(arrangements)?)?;
+            }
+        }
+        Some(sum)
+    }
+}
+
+impl<S> IndexCombinationsWithReplacement<S>
+    where S: AsRef<[usize]> + AsMut<[usize]>,
+{
+    /// Starts from the first and the last combination, of the length of `front`
+    /// and `back`.
+    fn new(n: usize, mut front: S, mut back: S) -> Self {
+        let k = front.as_ref().len();
+        for index in front.as_mut() {
+            *index = 0;
+        }
+        for index in back.as_mut() {
+            *index = n.saturating_sub(1);
+        }
+        IndexCombinationsWithReplacement { n, front, back, done: n == 0 && k > 0 }
+    }
+
+    /// Moves `front` to the next combination, which must exist.
+    fn increment(&mut self) {
+        let indices = self.front.as_mut();
+        let (n, k) = (self.n, indices.len());
+
+        // Scan from the end, looking for an index that can be increased
+        let mut i = k - 1;
+        while indices[i] == n - 1 {
+            i -= 1;
+        }
+
+        // Increment index, and reset the ones to its right to match it
+        indices[i] += 1;
+        let value = indices[i];
+        for index in &mut indices[i + 1..] {
+            *index = value;
+        }
+    }
+
+    /// Moves `back` to the previous combination, which must exist.
+    fn decrement(&mut self) {
+        let indices = self.back.as_mut();
+        let (n, k) = (self.n, indices.len());
+
+        // Scan from the end, looking for an index that can be decreased
+        let mut i = k - 1;
+        while i > 0 && indices[i] == indices[i - 1] {
+            i -= 1;
+        }
+
+        // Decrement index, and move the ones to its right as far up as they go
+        indices[i] -= 1;
+        for index in &mut indices[i + 1..] {
+            *index = n - 1;
+        }
+    }
+
+    /// Counts the combinations after `indices`, or `None` on overflow.
+    ///
+    /// Adding `i` to the index at position `i` maps these one to one and in
+    /// order onto the combinations without replacement of `0..n + k - 1`.
+    fn after(&self, indices: &[usize]) -> Option<usize> {
+        let k = indices.len();
+        indices.iter().enumerate().try_fold(0usize, |sum, (i, &index)| {
+            let top = (self.n - 1 - index).checked_add(k - 1 - i)?;
+            sum.checked_add(checked_binomial(top, k - i)?)
+        })
+    }
+}
+
+/// Sets the indices from position `start` on to the first of `candidates` that
+/// the ones before them do not hold, in order.
+///
+/// Each index is looked up among the `k` others, rather than in a table of the
+/// `n` possible ones, so that this takes no memory however large `n` is.
+fn take_unused<C>(indices: &mut [usize], start: usize, mut candidates: C)
+    where C: Iterator<Item = usize>,
+{
+    for i in start..indices.len() {
+        let (used, rest) = indices.split_at_mut(i);
+        rest[0] = candidates.by_ref().find(|value| !used.contains(value)).unwrap();
+    }
+}
+
+/// Counts the items from the front to the back inclusive, given how many
+/// follow each of them.
+fn remaining(front: Option<usize>, back: Option<usize>) -> (usize, Option<usize>) {
+    match (front, back) {
+        (Some(front), Some(back)) => match (front - back).checked_add(1) {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        },
+        // More than `usize::MAX` follow the front, so more than this are left
+        (None, Some(back)) => (usize::MAX - back, None),
+        _ => (0, None),
+    }
+}
+
+macro_rules! impl_index_iterator {
+    ($name:ident) => {
+        impl<S> Iterator for $name<S>
+            where S: AsRef<[usize]> + AsMut<[usize]> + Clone,
+        {
+            type Item = S;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.done {
+                    return None;
+                }
+
+                let item = self.front.clone();
+                if self.front.as_ref() == self.back.as_ref() {
+                    self.done = true;
+                } else {
+                    self.increment();
+                }
+                Some(item)
+            }
+
+            fn count(self) -> usize {
+                match self.size_hint() {
+                    (_, Some(count)) => count,
+                    _ => self.fold(0, |count, _| count + 1),
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                if self.done {
+                    return (0, Some(0));
+                }
+                remaining(self.after(self.front.as_ref()), self.after(self.back.as_ref()))
+            }
+        }
+
+        impl<S> DoubleEndedIterator for $name<S>
+            where S: AsRef<[usize]> + AsMut<[usize]> + Clone,
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.done {
+                    return None;
+                }
+
+                let item = self.back.clone();
+                if self.front.as_ref() == self.back.as_ref() {
+                    self.done = true;
+                } else {
+                    self.decrement();
+                }
+                Some(item)
+            }
+        }
+    };
+}
+
+impl_index_iterator!(IndexCombinations);
+impl_index_iterator!(IndexPermutations);
+impl_index_iterator!(IndexCombinationsWithReplacement);
@@ -42,7 +42,7 @@
 //!
 //! ## Rust Version
 //!
-//! This version of itertools requires Rust 1.32 or later.
+//! This version of itertools requires Rust 1.55 or later.
 #![doc(html_root_url="https://docs.rs/itertools/0.8/")]
 
 #[cfg(not(feature = "use_std"))]
@@ -64,8 +64,14 @@ use std::collections::HashMap;
 use std::iter::{IntoIterator, once};
 use std::cmp::Ordering;
 use std::fmt;
+#[cfg(feature = "use_alloc")]
+use std::ops::RangeBounds;
 #[cfg(feature = "use_std")]
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
+#[cfg(feature = "use_alloc")]
+use crate::group_map::NestedGroupKeys;
+#[cfg(feature = "use_alloc")]
+use crate::map_kind::MapKind;
 #[cfg(feature = "use_alloc")]
 use std::fmt::Write;
 #[cfg(feature = "use_alloc")]
@@ -108,33 +114,54 @@ pub mod structs {
     #[allow(deprecated)]
     pub use crate::adaptors::{MapResults, Step};
     #[cfg(feature = "use_alloc")]
-    pub use crate::adaptors::MultiProduct;
+    pub use crate::adaptors::{CartesianPower, CartesianPowerVec, DiagonalProduct, MultiDiagonalProduct,
+                              MultiProduct};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::array_combinations::ArrayCombinations;
+    pub use crate::bitmask::{Bits, MasksWithPopcount, SubsetsOfMask};
     #[cfg(feature = "use_alloc")]
-    pub use crate::combinations::Combinations;
+    pub use crate::chunk_by::{ChunkBy, ChunksVec};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::combinations::{Combinations, CombinationsPruned};
     #[cfg(feature = "use_alloc")]
     pub use crate::combinations_with_replacement::CombinationsWithReplacement;
     pub use crate::cons_tuples_impl::ConsTuples;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::derangements::Derangements;
     pub use crate::exactly_one_err::ExactlyOneError;
     pub use crate::format::{Format, FormatWith};
-    #[cfg(feature = "use_std")]
-    pub use crate::grouping_map::{GroupingMap, GroupingMapBy};
     #[cfg(feature = "use_alloc")]
-    pub use crate::groupbylazy::{IntoChunks, Chunk, Chunks, GroupBy, Group, Groups};
+    pub use crate::grouping_map::{GroupingMap, GroupingMapBy, MapForThenBy};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::groupbylazy::{IntoChunks, Chunk, Chunks, GroupBy, Group, Groups, BufferStats};
+    #[cfg(feature = "use_std")]
+    pub use crate::groupby_sync::{ChunkSync, ChunksSync, GroupBySync, GroupSync};
     pub use crate::intersperse::{Intersperse, IntersperseWith};
     #[cfg(feature = "use_alloc")]
+    pub use crate::index_combinatorics::{IndexCombinations, IndexCombinationsWithReplacement,
+                                         IndexPermutations};
+    #[cfg(feature = "use_alloc")]
     pub use crate::kmerge_impl::{KMerge, KMergeBy};
     pub use crate::merge_join::MergeJoinBy;
     #[cfg(feature = "use_alloc")]
     pub use crate::multipeek_impl::MultiPeek;
     #[cfg(feature = "use_alloc")]
+    pub use crate::multiset_permutations::MultisetPermutations;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::necklaces::Necklaces;
+    #[cfg(feature = "use_alloc")]
     pub use crate::peek_nth::PeekNth;
     pub use crate::pad_tail::PadUsing;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::partitions::{IntegerPartitions, SetPartitions};
     pub use crate::peeking_take_while::PeekingTakeWhile;
     #[cfg(feature = "use_alloc")]
     pub use crate::permutations::Permutations;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::permutation_swaps::PermutationSwaps;
     pub use crate::process_results_impl::ProcessResults;
     #[cfg(feature = "use_alloc")]
-    pub use crate::powerset::Powerset;
+    pub use crate::powerset::{Powerset, PowersetGray};
     #[cfg(feature = "use_alloc")]
     pub use crate::put_back_n_impl::PutBackN;
     #[cfg(feature = "use_alloc")]
@@ -146,28 +173,64 @@ pub mod structs {
     pub use crate::tee::Tee;
     pub use crate::tuple_impl::{TupleBuffer, TupleWindows, CircularTupleWindows, Tuples};
     #[cfg(feature = "use_std")]
-    pub use crate::unique_impl::{Unique, UniqueBy};
+    pub use crate::unique_impl::{Duplicates, DuplicatesBy, DuplicatesWithCount, Unique, UniqueBy};
+    #[cfg(feature = "use_std")]
+    pub use crate::unique_bounded::{UniqueApprox, UniqueRecent};
+    #[cfg(feature = "use_alloc")]
+    pub use crate::unique_in_impl::{UniqueIn, UniqueByIn};
     pub use crate::with_position::WithPosition;
     pub use crate::zip_eq_impl::ZipEq;
     pub use crate::zip_longest::ZipLongest;
     pub use crate::ziptuple::Zip;
 }
 
+#[cfg(feature = "use_alloc")]
+pub mod aggregations;
+
 /// Traits helpful for using certain `Itertools` methods in generic contexts.
 pub mod traits {
+    #[cfg(feature = "use_alloc")]
+    pub use crate::group_map::NestedGroupKeys;
+    #[cfg(feature = "use_alloc")]
+    pub use crate::map_kind::{MapKind, NestableMapKind};
+    #[cfg(feature = "use_std")]
+    pub use crate::map_kind::OrderableMapKind;
     pub use crate::tuple_impl::HomogeneousTuple;
 }
 
 #[allow(deprecated)]
 pub use crate::structs::*;
+pub use crate::bitmask::{IterBits, masks_with_popcount, subsets_of_mask};
 pub use crate::concat_impl::concat;
 pub use crate::cons_tuples_impl::cons_tuples;
 pub use crate::diff::diff_with;
 pub use crate::diff::Diff;
 #[cfg(feature = "use_alloc")]
+pub use crate::counts::{Counts, OrderedCounts};
+#[cfg(feature = "use_alloc")]
+pub use crate::groupbylazy::{BufferLimitError, BufferLimitPolicy};
+#[cfg(feature = "use_alloc")]
+pub use crate::index_combinatorics::{index_array_combinations,
+                                     index_array_combinations_with_replacement,
+                                     index_array_permutations, index_combinations,
+                                     index_combinations_with_replacement, index_permutations};
+#[cfg(feature = "use_alloc")]
 pub use crate::kmerge_impl::{kmerge_by};
+#[cfg(feature = "use_alloc")]
+pub use crate::map_kind::{BTreeMapKind, NestedMapKind};
+#[cfg(feature = "use_std")]
+pub use crate::map_kind::HashMapKind;
+#[cfg(feature = "use_std")]
+pub use crate::ordered_map::{OrderedMap, OrderedMapKind};
 pub use crate::minmax::MinMaxResult;
+#[cfg(feature = "use_alloc")]
+pub use crate::partitions::integer_partitions;
 pub use crate::peeking_take_while::PeekingNext;
+pub use crate::permutation_swaps::for_each_permutation;
+#[cfg(feature = "use_alloc")]
+pub use crate::permutation_swaps::permutation_swaps;
+#[cfg(feature = "use_alloc")]
+pub use crate::powerset::SubsetChange;
 pub use crate::process_results_impl::process_results;
 pub use crate::repeatn::repeat_n;
 #[allow(deprecated)]
@@ -175,6 +238,11 @@ pub use crate::sources::{repeat_call, unfold, iterate};
 pub use crate::with_position::Position;
 pub use crate::ziptuple::multizip;
 mod adaptors;
+#[cfg(feature = "use_alloc")]
+mod array_combinations;
+mod bitmask;
+#[cfg(feature = "use_alloc")]
+mod chunk_by;
 mod either_or_both;
 pub use crate::either_or_both::EitherOrBoth;
 #[doc(hidden)]
@@ -184,18 +252,26 @@ pub use crate::free::*;
 mod concat_impl;
 mod cons_tuples_impl;
 #[cfg(feature = "use_alloc")]
+mod derangements;
+#[cfg(feature = "use_alloc")]
 mod combinations;
 #[cfg(feature = "use_alloc")]
+mod counts;
+#[cfg(feature = "use_alloc")]
 mod combinations_with_replacement;
 mod exactly_one_err;
 mod diff;
 mod format;
-#[cfg(feature = "use_std")]
+#[cfg(feature = "use_alloc")]
 mod grouping_map;
 #[cfg(feature = "use_alloc")]
 mod group_map;
 #[cfg(feature = "use_alloc")]
 mod groupbylazy;
+#[cfg(feature = "use_std")]
+mod groupby_sync;
+#[cfg(feature = "use_alloc")]
+mod index_combinatorics;
 mod intersperse;
 #[cfg(feature = "use_alloc")]
 mod k_smallest;
@@ -203,14 +279,25 @@ mod k_smallest;
 mod kmerge_impl;
 #[cfg(feature = "use_alloc")]
 mod lazy_buffer;
+#[cfg(feature = "use_alloc")]
+mod map_kind;
 mod merge_join;
 mod minmax;
 #[cfg(feature = "use_alloc")]
 mod multipeek_impl;
+#[cfg(feature = "use_alloc")]
+mod multiset_permutations;
+#[cfg(feature = "use_alloc")]
+mod necklaces;
+#[cfg(feature = "use_std")]
+pub mod ordered_map;
 mod pad_tail;
 #[cfg(feature = "use_alloc")]
+mod partitions;
+#[cfg(feature = "use_alloc")]
 mod peek_nth;
 mod peeking_take_while;
+mod permutation_swaps;
 #[cfg(feature = "use_alloc")]
 mod permutations;
 #[cfg(feature = "use_alloc")]
@@ -224,10 +311,16 @@ mod repeatn;
 mod size_hint;
 mod sources;
 #[cfg(feature = "use_alloc")]
+mod split;
+#[cfg(feature = "use_alloc")]
 mod tee;
 mod tuple_impl;
 #[cfg(feature = "use_std")]
 mod unique_impl;
+#[cfg(feature = "use_std")]
+mod unique_bounded;
+#[cfg(feature = "use_alloc")]
+mod unique_in_impl;
 mod with_position;
 mod zip_eq_impl;
 mod zip_longest;
@@ -587,6 +680,141 @@ pub trait Itertools : Iterator {
         groupbylazy::new_chunks(self, size)
     }
 
+    /// Return an iterator adaptor that groups iterator elements like
+    /// [`.group_by()`](Itertools::group_by), and whose groups can be sent to
+    /// other threads.
+    ///
+    /// The groups share the iterator behind a `Mutex`, so they are `Send` when
+    /// the iterator, its elements, the keys and `key` are. They can be
+    /// consumed from different threads and in any order, with the same
+    /// buffering as `GroupBy`: a group's elements are only buffered if a later
+    /// group is requested before they are all consumed, and not at all if the
+    /// group was dropped.
+    ///
+    /// Unlike `GroupBy`, this is an iterator itself, and its groups own their
+    /// share of the iterator.
+    ///
+    /// Iterator element type is `(K, GroupSync)`: the group's key and the
+    /// group iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::thread;
+    ///
+    /// let log = vec![(1, "a"), (1, "b"), (2, "c"), (3, "d"), (3, "e")];
+    ///
+    /// // Hand each group to its own worker
+    /// let workers = log.into_iter()
+    ///     .group_by_sync(|&(id, _)| id)
+    ///     .map(|(id, group)| thread::spawn(move || (id, group.map(|(_, msg)| msg).collect::<String>())))
+    ///     .collect_vec();
+    /// let results = workers.into_iter().map(|worker| worker.join().unwrap()).collect_vec();
+    ///
+    /// assert_eq!(results, vec![(1, "ab".to_string()), (2, "c".to_string()), (3, "de".to_string())]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn group_by_sync<K, F>(self, key: F) -> GroupBySync<K, Self, F>
+        where Self: Sized,
+              F: FnMut(&Self::Item) -> K,
+              K: PartialEq,
+    {
+        groupby_sync::group_by_sync(self, key)
+    }
+
+    /// Return an iterator adaptor that chunks the iterator like
+    /// [`.chunks()`](Itertools::chunks), and whose chunks can be sent to
+    /// other threads.
+    ///
+    /// This is to `IntoChunks` what
+    /// [`.group_by_sync()`](Itertools::group_by_sync) is to `GroupBy`.
+    ///
+    /// Iterator element type is `ChunkSync`, each chunk's iterator.
+    ///
+    /// **Panics** if `size` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::thread;
+    ///
+    /// let data = vec![1, 1, 2, -2, 6, 0, 3, 1];
+    /// //chunk size=3 |------->|-------->|--->|
+    ///
+    /// let workers = data.into_iter()
+    ///     .chunks_sync(3)
+    ///     .map(|chunk| thread::spawn(move || chunk.sum::<i32>()))
+    ///     .collect_vec();
+    /// for worker in workers {
+    ///     assert_eq!(worker.join().unwrap(), 4);
+    /// }
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn chunks_sync(self, size: usize) -> ChunksSync<Self>
+        where Self: Sized,
+    {
+        assert!(size != 0);
+        groupby_sync::chunks_sync(self, size)
+    }
+
+    /// Return an iterator adaptor that collects each run of consecutive
+    /// elements that map to the same key into a `Vec`.
+    ///
+    /// Unlike [`.group_by()`](Itertools::group_by), this is a plain iterator
+    /// that owns its state, so it can be returned, stored and chained like any
+    /// other adaptor, at the cost of buffering each run.
+    ///
+    /// Iterator element type is `(K, Vec<Self::Item>)`: the run's key and its
+    /// elements.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // group data into runs of larger than zero or not.
+    /// let data = vec![1, 3, -2, -2, 1, 0, 1, 2];
+    /// // groups:     |---->|------>|--------->|
+    ///
+    /// itertools::assert_equal(data.into_iter().chunk_by(|elt| *elt >= 0), vec![
+    ///     (true, vec![1, 3]),
+    ///     (false, vec![-2, -2]),
+    ///     (true, vec![1, 0, 1, 2]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn chunk_by<K, F>(self, key: F) -> ChunkBy<Self, K, F>
+        where Self: Sized,
+              F: FnMut(&Self::Item) -> K,
+              K: PartialEq,
+    {
+        chunk_by::chunk_by(self, key)
+    }
+
+    /// Return an iterator adaptor that collects each `size` consecutive
+    /// elements into a `Vec`. The last chunk will be shorter if there aren't
+    /// enough elements.
+    ///
+    /// Unlike [`.chunks()`](Itertools::chunks), this is a plain iterator that
+    /// owns its state, so it can be returned, stored and chained like any
+    /// other adaptor, at the cost of buffering each chunk.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`.
+    ///
+    /// **Panics** if `size` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![1, 1, 2, -2, 6, 0, 3, 1];
+    /// //chunk size=3 |------->|-------->|--->|
+    ///
+    /// let sums = data.into_iter().chunks_vec(3).map(|chunk| chunk.iter().sum::<i32>());
+    /// itertools::assert_equal(sums, vec![4, 4, 4]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn chunks_vec(self, size: usize) -> ChunksVec<Self>
+        where Self: Sized,
+    {
+        chunk_by::chunks_vec(self, size)
+    }
+
     /// Return an iterator over all contiguous windows producing tuples of
     /// a specific size (up to 4).
     ///
@@ -1019,6 +1247,141 @@ pub trait Itertools : Iterator {
         adaptors::multi_cartesian_product(self)
     }
 
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// the element sets of two iterators `self` and `J`, one anti-diagonal at a
+    /// time.
+    ///
+    /// Iterator element type is `(Self::Item, J::Item)`.
+    ///
+    /// Pairs are produced in order of the sum of the positions of their elements,
+    /// `(a0, b0)`, then `(a0, b1)`, `(a1, b0)`, then `(a0, b2)`, `(a1, b1)`, `(a2, b0)`
+    /// and so on, like Cantor's enumeration of pairs. Unlike
+    /// [`.cartesian_product()`](Itertools::cartesian_product), every pair is
+    /// reached after finitely many steps even when both iterators are infinite.
+    ///
+    /// Neither iterator needs to be `Clone`: each one is read lazily, one element
+    /// further for each new diagonal, and its elements are buffered and cloned
+    /// into the pairs.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..).diagonal_product("abc".chars());
+    /// itertools::assert_equal(it.take(7), vec![
+    ///     (0, 'a'),
+    ///     (0, 'b'), (1, 'a'),
+    ///     (0, 'c'), (1, 'b'), (2, 'a'),
+    ///     (1, 'c'),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn diagonal_product<J>(self, other: J) -> DiagonalProduct<Self, J::IntoIter>
+        where Self: Sized,
+              Self::Item: Clone,
+              J: IntoIterator,
+              J::Item: Clone
+    {
+        adaptors::diagonal_product(self, other.into_iter())
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of
+    /// all subiterators returned by meta-iterator `self`, one anti-diagonal at a
+    /// time.
+    ///
+    /// This is the n-ary version of
+    /// [`.diagonal_product()`](Itertools::diagonal_product): tuples are produced
+    /// in order of the sum of the positions of their elements, and in
+    /// lexicographic order of the positions for the same sum. Every tuple is
+    /// reached after finitely many steps, even when the subiterators are infinite.
+    /// Like [`.multi_cartesian_product()`](Itertools::multi_cartesian_product),
+    /// an empty meta-iterator yields nothing.
+    ///
+    /// The iterator element type is `Vec<T>`, where `T` is the iterator element
+    /// of the subiterators.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let mut it = (0..3).map(|_| 0..).multi_diagonal_product();
+    /// assert_eq!(it.next(), Some(vec![0, 0, 0]));
+    /// assert_eq!(it.next(), Some(vec![0, 0, 1]));
+    /// assert_eq!(it.next(), Some(vec![0, 1, 0]));
+    /// assert_eq!(it.next(), Some(vec![1, 0, 0]));
+    /// assert_eq!(it.next(), Some(vec![0, 0, 2]));
+    /// assert_eq!(it.next(), Some(vec![0, 1, 1]));
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn multi_diagonal_product(self) -> MultiDiagonalProduct<<Self::Item as IntoIterator>::IntoIter>
+        where Self: Iterator + Sized,
+              Self::Item: IntoIterator,
+              <Self::Item as IntoIterator>::Item: Clone
+    {
+        adaptors::multi_diagonal_product(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of `N`
+    /// copies of the iterator, i.e. `self^N`, as arrays.
+    ///
+    /// Iterator element type is `[Self::Item; N]`. The elements of the source are
+    /// buffered the first time they are reached and cloned into each item, so
+    /// the iterator itself does not need to be `Clone` and is only walked once.
+    /// Items are produced in lexicographic order of the positions of their
+    /// elements in the source, like
+    /// [`.multi_cartesian_product()`](Itertools::multi_cartesian_product) on `N`
+    /// copies of it would.
+    ///
+    /// The power `N = 0` yields a single empty array.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = "ab".chars().cartesian_power::<3>();
+    /// itertools::assert_equal(it, vec![
+    ///     ['a', 'a', 'a'],
+    ///     ['a', 'a', 'b'],
+    ///     ['a', 'b', 'a'],
+    ///     ['a', 'b', 'b'],
+    ///     ['b', 'a', 'a'],
+    ///     ['b', 'a', 'b'],
+    ///     ['b', 'b', 'a'],
+    ///     ['b', 'b', 'b'],
+    /// ]);
+    /// assert_eq!((0..10).cartesian_power::<4>().len(), 10_000);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn cartesian_power<const N: usize>(self) -> CartesianPower<Self, N>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        adaptors::cartesian_power(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the cartesian product of `n`
+    /// copies of the iterator, i.e. `self^n`, as `Vec`s.
+    ///
+    /// This is like [`.cartesian_power()`](Itertools::cartesian_power) for a power
+    /// only known at runtime. Iterator element type is `Vec<Self::Item>`, and the
+    /// iterator produces a new `Vec` per iteration.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (0..2).cartesian_power_vec(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![0, 0],
+    ///     vec![0, 1],
+    ///     vec![1, 0],
+    ///     vec![1, 1],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn cartesian_power_vec(self, n: usize) -> CartesianPowerVec<Self>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        adaptors::cartesian_power_vec(self, n)
+    }
+
     /// Return an iterator adaptor that uses the passed-in closure to
     /// optionally merge together consecutive elements.
     ///
@@ -1195,6 +1558,228 @@ pub trait Itertools : Iterator {
         unique_impl::unique_by(self, f)
     }
 
+    /// Return an iterator adaptor that filters out elements equal to one of
+    /// the last `n` distinct elements seen during the iteration.
+    ///
+    /// Unlike [`.unique()`](Itertools::unique), this only remembers `n`
+    /// elements, so it can deduplicate unbounded streams: clones of the `n`
+    /// most recently seen distinct elements are kept in a hash map, and the
+    /// least recently seen one is forgotten to make room for a new one. An
+    /// element that is filtered out counts as seen again, so that runs of
+    /// duplicates, however long, are all filtered out.
+    ///
+    /// An element is produced again once `n` other distinct elements were
+    /// seen since it last was. With `n` zero, every element is produced.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![1, 2, 1, 3, 4, 1, 2, 2, 5];
+    /// itertools::assert_equal(data.into_iter().unique_recent(2),
+    ///                         vec![1, 2, 3, 4, 1, 2, 5]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn unique_recent(self, n: usize) -> UniqueRecent<Self>
+        where Self: Sized,
+              Self::Item: Clone + Eq + Hash
+    {
+        unique_bounded::unique_recent(self, n)
+    }
+
+    /// Return an iterator adaptor that filters out elements that have
+    /// already been produced once during the iteration, and possibly a few
+    /// others, using a fixed amount of memory.
+    ///
+    /// The produced elements are remembered in a Bloom filter of `bits` bits,
+    /// each element setting up to `hashes` of them, and an element whose bits
+    /// are all set already is filtered out. Duplicates are always detected,
+    /// but a new element is wrongly taken for a duplicate with a probability
+    /// that grows with the number `n` of elements produced so far, about
+    ///
+    /// `(1 - exp(-hashes * n / bits))^hashes`.
+    ///
+    /// For a given number of bits per element, this is smallest with
+    /// `hashes = bits / n * ln(2)`: for example, 10 bits per element and
+    /// 7 hashes filter out about 1 new element in 120, and 20 bits per element
+    /// and 14 hashes about 1 in 15 000. The estimated rate is available from
+    /// [`UniqueApprox::false_positive_rate`].
+    ///
+    /// The elements are hashed with a random seed, so which ones are filtered
+    /// out wrongly varies from run to run. Use
+    /// [`.unique_approx_with_hasher()`](Itertools::unique_approx_with_hasher)
+    /// for the same results every time.
+    ///
+    /// **Panics** if `bits` or `hashes` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![10, 20, 30, 20, 40, 10, 50];
+    /// let mut unique = data.into_iter().unique_approx(1 << 16, 8);
+    /// itertools::assert_equal(unique.by_ref(), vec![10, 20, 30, 40, 50]);
+    /// assert!(unique.false_positive_rate() < 1e-15);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn unique_approx(self, bits: usize, hashes: u32) -> UniqueApprox<Self>
+        where Self: Sized,
+              Self::Item: Hash
+    {
+        unique_bounded::unique_approx(self, bits, hashes)
+    }
+
+    /// Return an iterator adaptor that filters out elements that have
+    /// already been produced once during the iteration, and possibly a few
+    /// others, using a fixed amount of memory and the given hasher.
+    ///
+    /// This is like [`.unique_approx()`](Itertools::unique_approx), but the
+    /// Bloom filter hashes the elements with `hasher`. With a hasher that is
+    /// not randomly seeded, the same elements are always filtered out.
+    ///
+    /// **Panics** if `bits` or `hashes` is 0.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    /// let data = vec![10, 20, 30, 20, 40, 10, 50];
+    /// itertools::assert_equal(data.into_iter().unique_approx_with_hasher(1 << 16, 8, hasher),
+    ///                         vec![10, 20, 30, 40, 50]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn unique_approx_with_hasher<S>(self, bits: usize, hashes: u32, hasher: S)
+        -> UniqueApprox<Self, S>
+        where Self: Sized,
+              Self::Item: Hash,
+              S: BuildHasher,
+    {
+        unique_bounded::unique_approx_with_hasher(self, bits, hashes, hasher)
+    }
+
+    /// Return an iterator adaptor that produces the elements that are
+    /// duplicates of earlier ones, each the first time it is seen for the
+    /// second time. Duplicates are detected using hash and equality.
+    ///
+    /// Clones of visited elements are stored in a hash map in the iterator.
+    ///
+    /// The iterator is stable, returning the duplicate items in the order in
+    /// which their second occurrences come in the adapted iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![10, 20, 30, 20, 40, 10, 50, 10];
+    /// itertools::assert_equal(data.into_iter().duplicates(),
+    ///                         vec![20, 10]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn duplicates(self) -> Duplicates<Self>
+        where Self: Sized,
+              Self::Item: Clone + Eq + Hash
+    {
+        unique_impl::duplicates(self)
+    }
+
+    /// Return an iterator adaptor that produces the elements whose key is a
+    /// duplicate of an earlier element's, each the first time the key is seen
+    /// for the second time.
+    ///
+    /// Duplicates are detected by comparing the key they map to with the
+    /// keying function `f` by hash and equality. The keys are stored in a hash
+    /// map in the iterator.
+    ///
+    /// The iterator is stable, returning the duplicate items in the order in
+    /// which they occur in the adapted iterator.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec!["a", "bb", "aa", "c", "ccc", "dd"];
+    /// itertools::assert_equal(data.into_iter().duplicates_by(|s| s.len()),
+    ///                         vec!["aa", "c"]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn duplicates_by<V, F>(self, f: F) -> DuplicatesBy<Self, V, F>
+        where Self: Sized,
+              V: Eq + Hash,
+              F: FnMut(&Self::Item) -> V
+    {
+        unique_impl::duplicates_by(self, f)
+    }
+
+    /// Consume the iterator and return an iterator over the elements that
+    /// occur in it more than once, each with its total number of occurrences.
+    /// Duplicates are detected using hash and equality.
+    ///
+    /// The elements are counted in a hash map, and come in an unspecified
+    /// order.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![10, 20, 30, 20, 40, 10, 50, 10];
+    /// let duplicates = data.into_iter().duplicates_with_count().sorted();
+    /// itertools::assert_equal(duplicates, vec![(10, 3), (20, 2)]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn duplicates_with_count(self) -> DuplicatesWithCount<Self::Item>
+        where Self: Sized,
+              Self::Item: Eq + Hash
+    {
+        unique_impl::duplicates_with_count(self)
+    }
+
+    /// Return an iterator adaptor that filters out elements that have
+    /// already been produced once during the iteration, remembering them in a
+    /// map of the given kind.
+    ///
+    /// This is like [`.unique()`](Itertools::unique), but the visited elements
+    /// can be kept in any [`MapKind`](crate::traits::MapKind). With
+    /// [`BTreeMapKind`](crate::BTreeMapKind), duplicates are detected by
+    /// ordering rather than by hash, which also works without `std`, where
+    /// `.unique()` is not available.
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let data = vec![10, 20, 30, 20, 40, 10, 50];
+    /// itertools::assert_equal(data.into_iter().unique_in(BTreeMapKind),
+    ///                         vec![10, 20, 30, 40, 50]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn unique_in<M>(self, kind: M) -> UniqueIn<Self, M>
+        where Self: Sized,
+              Self::Item: Clone,
+              M: MapKind<Self::Item, ()>,
+    {
+        unique_in_impl::unique_in(self, kind)
+    }
+
+    /// Return an iterator adaptor that filters out elements that have
+    /// already been produced once during the iteration, remembering the keys
+    /// they map to with the keying function `f` in a map of the given kind.
+    ///
+    /// This is like [`.unique_by()`](Itertools::unique_by), but the keys can
+    /// be kept in any [`MapKind`](crate::traits::MapKind), such as
+    /// [`BTreeMapKind`](crate::BTreeMapKind) for `Ord` keys.
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let data = vec!["a", "bb", "aa", "c", "ccc"];
+    /// itertools::assert_equal(data.into_iter().unique_by_in(BTreeMapKind, |s| s.len()),
+    ///                         vec!["a", "bb", "ccc"]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn unique_by_in<V, F, M>(self, kind: M, f: F) -> UniqueByIn<Self, V, F, M>
+        where Self: Sized,
+              F: FnMut(&Self::Item) -> V,
+              M: MapKind<V, ()>,
+    {
+        unique_in_impl::unique_by_in(self, kind, f)
+    }
+
     /// Return an iterator adaptor that borrows from this iterator and
     /// takes items while the closure `accept` returns `true`.
     ///
@@ -1337,6 +1922,92 @@ pub trait Itertools : Iterator {
         combinations::combinations(self, k)
     }
 
+    /// Return an iterator adaptor that iterates over the `k`-length combinations
+    /// of the elements from an iterator, skipping those with a rejected prefix.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Combinations are produced in
+    /// the same order as [`.combinations()`](Itertools::combinations), but each
+    /// one is built up one element at a time, and `accept_prefix` is called with
+    /// the partial combination every time it is extended, from length 1 up to
+    /// `k`. Once a prefix is rejected, every combination starting with it is
+    /// skipped without being generated, which makes this suitable for
+    /// branch-and-bound searches.
+    ///
+    /// A prefix is only passed to `accept_prefix` after all of its own prefixes
+    /// were accepted, and the same prefix may be passed again after a later
+    /// element changes.
+    ///
+    /// Pruning skips the combinations of a rejected prefix, but not the
+    /// elements of the source: the search still tries each of them in turn to
+    /// extend a prefix. Over an infinite source, once every extension of a
+    /// prefix is rejected, the search for the next combination never
+    /// terminates.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// // Subsets of three weights with a total of at most 10.
+    /// let weights = vec![2, 3, 5, 7, 11];
+    /// let it = weights.into_iter()
+    ///     .combinations_pruned(3, |prefix| prefix.iter().sum::<i32>() <= 10);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![2, 3, 5],
+    /// ]);
+    ///
+    /// // Combinations alternating between even and odd elements.
+    /// let it = (0..6).combinations_pruned(3, |prefix| match prefix {
+    ///     [.., a, b] => a % 2 != b % 2,
+    ///     _ => true,
+    /// });
+    /// itertools::assert_equal(it.take(4), vec![
+    ///     vec![0, 1, 2],
+    ///     vec![0, 1, 4],
+    ///     vec![0, 3, 4],
+    ///     vec![1, 2, 3],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn combinations_pruned<F>(self, k: usize, accept_prefix: F) -> CombinationsPruned<Self, F>
+        where Self: Sized,
+              Self::Item: Clone,
+              F: FnMut(&[Self::Item]) -> bool
+    {
+        combinations::combinations_pruned(self, k, accept_prefix)
+    }
+
+    /// Return an iterator adaptor that iterates over the `K`-length combinations of
+    /// the elements from an iterator, as arrays.
+    ///
+    /// Iterator element type is `[Self::Item; K]`. Unlike
+    /// [`.combinations()`](Itertools::combinations), no `Vec` is allocated per
+    /// iteration; the iterator elements are still cloned. The length `K` is a
+    /// constant and is often inferred from how the combinations are used.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = (1..5).array_combinations::<3>();
+    /// itertools::assert_equal(it, vec![
+    ///     [1, 2, 3],
+    ///     [1, 2, 4],
+    ///     [1, 3, 4],
+    ///     [2, 3, 4],
+    /// ]);
+    ///
+    /// let mut sums = vec![];
+    /// for [a, b] in (1..4).array_combinations() {
+    ///     sums.push(a + b);
+    /// }
+    /// assert_eq!(sums, vec![3, 4, 5]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn array_combinations<const K: usize>(self) -> ArrayCombinations<Self, K>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        array_combinations::array_combinations(self)
+    }
+
     /// Return an iterator that iterates over the `k`-length combinations of
     /// the elements from an iterator, with replacement.
     ///
@@ -1357,57 +2028,214 @@ pub trait Itertools : Iterator {
     /// ]);
     /// ```
     #[cfg(feature = "use_alloc")]
-    fn combinations_with_replacement(self, k: usize) -> CombinationsWithReplacement<Self>
-    where
-        Self: Sized,
-        Self::Item: Clone,
+    fn combinations_with_replacement(self, k: usize) -> CombinationsWithReplacement<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        combinations_with_replacement::combinations_with_replacement(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over all k-permutations of the
+    /// elements from an iterator.
+    ///
+    /// Iterator element type is `Vec<Self::Item>` with length `k`. The iterator
+    /// produces a new Vec per iteration, and clones the iterator elements.
+    ///
+    /// If `k` is greater than the length of the input iterator, the resultant
+    /// iterator adaptor will be empty.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let perms = (5..8).permutations(2);
+    /// itertools::assert_equal(perms, vec![
+    ///     vec![5, 6],
+    ///     vec![5, 7],
+    ///     vec![6, 5],
+    ///     vec![6, 7],
+    ///     vec![7, 5],
+    ///     vec![7, 6],
+    /// ]);
+    /// ```
+    ///
+    /// Note: Permutations does not take into account the equality of the iterated values.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let it = vec![2, 2].into_iter().permutations(2);
+    /// itertools::assert_equal(it, vec![
+    ///     vec![2, 2], // Note: these are the same
+    ///     vec![2, 2], // Note: these are the same
+    /// ]);
+    /// ```
+    ///
+    /// Note: The source iterator is collected lazily, and will not be
+    /// re-iterated if the permutations adaptor is completed and re-iterated.
+    #[cfg(feature = "use_alloc")]
+    fn permutations(self, k: usize) -> Permutations<Self>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        permutations::permutations(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the derangements of the
+    /// elements from an iterator: the permutations of all of them that leave none
+    /// in its original position.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Like
+    /// [`.permutations()`](Itertools::permutations), elements are told apart by
+    /// position, not by value, and derangements are produced in lexicographic
+    /// order of the positions they take elements from. They are generated
+    /// directly, without going through the other permutations.
+    ///
+    /// The source iterator is read completely on the first call to `next`, after
+    /// which `size_hint` is exact as long as the count fits in `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal("abc".chars().derangements(), vec![
+    ///     vec!['b', 'c', 'a'],
+    ///     vec!['c', 'a', 'b'],
+    /// ]);
+    /// assert_eq!((0..6).derangements().count(), 265);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn derangements(self) -> Derangements<Self>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        derangements::derangements(self)
+    }
+
+    /// Return an iterator adaptor that iterates over the necklaces of length `k`
+    /// over the elements from an iterator: the sequences of `k` of them,
+    /// repetitions allowed, that are distinct up to rotation.
+    ///
+    /// Iterator element type is `Vec<Self::Item>`. Each necklace is produced once,
+    /// as its smallest rotation in order of the positions of the elements in the
+    /// source, and necklaces are produced in that order too. They are generated
+    /// directly with the algorithm of Fredricksen, Kessler and Maiorana.
+    ///
+    /// The source iterator is read completely on the first call to `next`, after
+    /// which `size_hint` is exact as long as the count fits in `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal("ab".chars().necklaces(4), vec![
+    ///     vec!['a', 'a', 'a', 'a'],
+    ///     vec!['a', 'a', 'a', 'b'],
+    ///     vec!['a', 'a', 'b', 'b'],
+    ///     vec!['a', 'b', 'a', 'b'],
+    ///     vec!['a', 'b', 'b', 'b'],
+    ///     vec!['b', 'b', 'b', 'b'],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn necklaces(self, k: usize) -> Necklaces<Self>
+        where Self: Sized,
+              Self::Item: Clone
+    {
+        necklaces::necklaces(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the bracelets of length `k`
+    /// over the elements from an iterator: the sequences of `k` of them,
+    /// repetitions allowed, that are distinct up to rotation and reflection.
+    ///
+    /// This is like [`.necklaces()`](Itertools::necklaces), keeping only the
+    /// necklaces that are no larger than their own reflection.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// itertools::assert_equal((0..3).bracelets(3), vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 0, 2],
+    ///     vec![0, 1, 1],
+    ///     vec![0, 1, 2],
+    ///     vec![0, 2, 2],
+    ///     vec![1, 1, 1],
+    ///     vec![1, 1, 2],
+    ///     vec![1, 2, 2],
+    ///     vec![2, 2, 2],
+    /// ]);
+    /// // As necklaces, [0, 1, 2] and [0, 2, 1] are distinct.
+    /// assert_eq!((0..3).necklaces(3).count(), 11);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn bracelets(self, k: usize) -> Necklaces<Self>
+        where Self: Sized,
+              Self::Item: Clone
     {
-        combinations_with_replacement::combinations_with_replacement(self, k)
+        necklaces::bracelets(self, k)
     }
 
-    /// Return an iterator adaptor that iterates over all k-permutations of the
-    /// elements from an iterator.
+    /// Return an iterator adaptor that iterates over the distinct k-permutations
+    /// of the elements from an iterator, treating equal elements as
+    /// interchangeable.
     ///
     /// Iterator element type is `Vec<Self::Item>` with length `k`. The iterator
     /// produces a new Vec per iteration, and clones the iterator elements.
     ///
-    /// If `k` is greater than the length of the input iterator, the resultant
-    /// iterator adaptor will be empty.
+    /// Each distinct arrangement is produced exactly once, in lexicographic order.
+    /// The exact number of permutations is known up front, so `size_hint` and
+    /// `count` are exact (as long as the count fits in `usize`).
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let perms = (5..8).permutations(2);
+    /// let perms = vec![2, 1, 1].into_iter().multiset_permutations(3);
     /// itertools::assert_equal(perms, vec![
-    ///     vec![5, 6],
-    ///     vec![5, 7],
-    ///     vec![6, 5],
-    ///     vec![6, 7],
-    ///     vec![7, 5],
-    ///     vec![7, 6],
+    ///     vec![1, 1, 2],
+    ///     vec![1, 2, 1],
+    ///     vec![2, 1, 1],
     /// ]);
+    ///
+    /// let perms = vec![1, 1, 2].into_iter().multiset_permutations(2);
+    /// assert_eq!(perms.count(), 3);
     /// ```
     ///
-    /// Note: Permutations does not take into account the equality of the iterated values.
+    /// Note: The source iterator is collected eagerly.
+    #[cfg(feature = "use_alloc")]
+    fn multiset_permutations(self, k: usize) -> MultisetPermutations<Self::Item>
+        where Self: Sized,
+              Self::Item: Clone + Ord
+    {
+        multiset_permutations::multiset_permutations(self, k)
+    }
+
+    /// Return an iterator adaptor that iterates over the distinct k-permutations
+    /// of the elements from an iterator, for elements that are `Eq + Hash`
+    /// rather than `Ord`.
+    ///
+    /// This is like [`.multiset_permutations()`](Itertools::multiset_permutations),
+    /// except that the lexicographic order is the order in which each distinct
+    /// element first appears in the source.
     ///
     /// ```
     /// use itertools::Itertools;
     ///
-    /// let it = vec![2, 2].into_iter().permutations(2);
-    /// itertools::assert_equal(it, vec![
-    ///     vec![2, 2], // Note: these are the same
-    ///     vec![2, 2], // Note: these are the same
+    /// let perms = vec!['b', 'a', 'b'].into_iter().multiset_permutations_hash(3);
+    /// itertools::assert_equal(perms, vec![
+    ///     vec!['b', 'b', 'a'],
+    ///     vec!['b', 'a', 'b'],
+    ///     vec!['a', 'b', 'b'],
     /// ]);
     /// ```
     ///
-    /// Note: The source iterator is collected lazily, and will not be
-    /// re-iterated if the permutations adaptor is completed and re-iterated.
-    #[cfg(feature = "use_alloc")]
-    fn permutations(self, k: usize) -> Permutations<Self>
+    /// Note: The source iterator is collected eagerly.
+    #[cfg(feature = "use_std")]
+    fn multiset_permutations_hash(self, k: usize) -> MultisetPermutations<Self::Item>
         where Self: Sized,
-              Self::Item: Clone
+              Self::Item: Clone + Eq + Hash
     {
-        permutations::permutations(self, k)
+        multiset_permutations::multiset_permutations_hash(self, k)
     }
 
     /// Return an iterator that iterates through the powerset of the elements from an
@@ -1446,6 +2274,137 @@ pub trait Itertools : Iterator {
         powerset::powerset(self)
     }
 
+    /// Return an iterator that iterates through the subsets of the elements from an
+    /// iterator whose size lies in `sizes`.
+    ///
+    /// This is like [`.powerset()`](Itertools::powerset), but skips every subset
+    /// that is smaller or larger than `sizes` allows. Subsets are produced in the
+    /// same order, by increasing size.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let sets = (1..5).powerset_range(2..=3);
+    /// itertools::assert_equal(sets, vec![
+    ///     vec![1, 2],
+    ///     vec![1, 3],
+    ///     vec![1, 4],
+    ///     vec![2, 3],
+    ///     vec![2, 4],
+    ///     vec![3, 4],
+    ///     vec![1, 2, 3],
+    ///     vec![1, 2, 4],
+    ///     vec![1, 3, 4],
+    ///     vec![2, 3, 4],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_range<R>(self, sizes: R) -> Powerset<Self>
+        where Self: Sized,
+              Self::Item: Clone,
+              R: RangeBounds<usize>,
+    {
+        powerset::powerset_range(self, sizes)
+    }
+
+    /// Return an iterator that walks through the powerset of the elements from an
+    /// iterator in Gray-code order, adding or removing a single element at each
+    /// step.
+    ///
+    /// Iterator element type is `(SubsetChange<Self::Item>, Vec<Self::Item>)`: the
+    /// element that changed, and the subset after the change. The subset keeps
+    /// the elements in the order of the source iterator.
+    ///
+    /// The walk starts from the empty set, which is not yielded itself, and then
+    /// reaches each of the other _2^n - 1_ subsets exactly once, following the
+    /// binary reflected Gray code. The source iterator is only advanced when one
+    /// of its elements is added for the first time.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    /// use itertools::SubsetChange::{Added, Removed};
+    ///
+    /// let steps = (1..4).powerset_gray();
+    /// itertools::assert_equal(steps, vec![
+    ///     (Added(1), vec![1]),
+    ///     (Added(2), vec![1, 2]),
+    ///     (Removed(1), vec![2]),
+    ///     (Added(3), vec![2, 3]),
+    ///     (Added(1), vec![1, 2, 3]),
+    ///     (Removed(2), vec![1, 3]),
+    ///     (Removed(1), vec![3]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn powerset_gray(self) -> PowersetGray<Self>
+        where Self: Sized,
+              Self::Item: Clone,
+    {
+        powerset::powerset_gray(self)
+    }
+
+    /// Return an iterator that iterates through the partitions of the elements from
+    /// an iterator into non-empty blocks.
+    ///
+    /// Iterator element type is `Vec<Vec<Self::Item>>`. The iterator produces new
+    /// `Vec`s per iteration, and clones the iterator elements.
+    ///
+    /// Blocks are listed in order of their first element, and the elements of a
+    /// block are in the order of the source iterator. There are _Bell(n)_
+    /// partitions of _n_ elements, which `size_hint` reports exactly as long as it
+    /// fits in `usize`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let partitions = (1..4).set_partitions();
+    /// itertools::assert_equal(partitions, vec![
+    ///     vec![vec![1, 2, 3]],
+    ///     vec![vec![1, 2], vec![3]],
+    ///     vec![vec![1, 3], vec![2]],
+    ///     vec![vec![1], vec![2, 3]],
+    ///     vec![vec![1], vec![2], vec![3]],
+    /// ]);
+    /// ```
+    ///
+    /// Note: Every partition contains all the elements, so the whole source iterator
+    /// is collected on the first call to `next`.
+    #[cfg(feature = "use_alloc")]
+    fn set_partitions(self) -> SetPartitions<Self>
+        where Self: Sized,
+              Self::Item: Clone,
+    {
+        partitions::set_partitions(self)
+    }
+
+    /// Return an iterator that iterates through the partitions of the elements from
+    /// an iterator into exactly `k` non-empty blocks.
+    ///
+    /// This is like [`.set_partitions()`](Itertools::set_partitions), restricted to
+    /// the _S(n, k)_ partitions with `k` blocks (a Stirling number of the second
+    /// kind). They are generated directly, not by filtering all the partitions.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let partitions = (1..5).set_partitions_k(3);
+    /// itertools::assert_equal(partitions, vec![
+    ///     vec![vec![1, 2], vec![3], vec![4]],
+    ///     vec![vec![1, 3], vec![2], vec![4]],
+    ///     vec![vec![1], vec![2, 3], vec![4]],
+    ///     vec![vec![1, 4], vec![2], vec![3]],
+    ///     vec![vec![1], vec![2, 4], vec![3]],
+    ///     vec![vec![1], vec![2], vec![3, 4]],
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn set_partitions_k(self, k: usize) -> SetPartitions<Self>
+        where Self: Sized,
+              Self::Item: Clone,
+    {
+        partitions::set_partitions_k(self, k)
+    }
+
     /// Return an iterator adaptor that pads the sequence to a minimum length of
     /// `min` by filling missing elements using a closure `f`.
     ///
@@ -2551,6 +3510,183 @@ pub trait Itertools : Iterator {
         group_map::into_group_map_by(self, f)
     }
 
+    /// Return an [`OrderedMap`] of keys mapped to `Vec`s of values, with the
+    /// keys in the order they are first seen. Keys and values are taken from
+    /// `(Key, Value)` tuple pairs yielded by the input iterator.
+    ///
+    /// This is like [`.into_group_map()`](Itertools::into_group_map), but the
+    /// groups always come out in the same order.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let data = vec![(3, 13), (0, 10), (2, 12), (0, 20), (3, 33), (2, 42)];
+    /// let lookup = data.into_iter().into_ordered_group_map();
+    ///
+    /// assert_eq!(lookup[&0], vec![10, 20]);
+    /// assert_eq!(lookup.get(&1), None);
+    /// itertools::assert_equal(lookup.keys(), &[3, 0, 2]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn into_ordered_group_map<K, V>(self) -> OrderedMap<K, Vec<V>>
+        where Self: Iterator<Item=(K, V)> + Sized,
+              K: Hash + Eq,
+    {
+        group_map::into_group_map_in(self, OrderedMapKind::default())
+    }
+
+    /// Return an [`OrderedMap`] of keys mapped to `Vec`s of the values that
+    /// map to them with the keying function `f`, with the keys in the order
+    /// they are first seen.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let words = vec!["one", "two", "three", "four", "five", "six"];
+    /// let lookup = words.into_iter().into_ordered_group_map_by(|word| word.len());
+    ///
+    /// itertools::assert_equal(lookup, vec![
+    ///     (3, vec!["one", "two", "six"]),
+    ///     (5, vec!["three"]),
+    ///     (4, vec!["four", "five"]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn into_ordered_group_map_by<K, V, F>(self, f: F) -> OrderedMap<K, Vec<V>>
+        where Self: Iterator<Item=V> + Sized,
+              K: Hash + Eq,
+              F: Fn(&V) -> K,
+    {
+        group_map::into_group_map_by_in(self, OrderedMapKind::default(), f)
+    }
+
+    /// Return a map of the given kind, of keys mapped to `Vec`s of values.
+    ///
+    /// This is like [`.into_group_map()`](Itertools::into_group_map), but
+    /// collects in maps of any [`MapKind`](crate::traits::MapKind), such as
+    /// `BTreeMap`s to get the keys in order, or `HashMap`s with another hasher.
+    /// Without `std`, `.into_group_map_in(BTreeMapKind)` replaces
+    /// `.into_group_map()`.
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let data = vec![(3, 13), (0, 10), (2, 12), (0, 20), (3, 33), (2, 42)];
+    /// let lookup = data.into_iter().into_group_map_in(BTreeMapKind);
+    ///
+    /// itertools::assert_equal(lookup, vec![
+    ///     (0, vec![10, 20]),
+    ///     (2, vec![12, 42]),
+    ///     (3, vec![13, 33]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_group_map_in<K, V, M>(self, kind: M) -> M::Map
+        where Self: Iterator<Item=(K, V)> + Sized,
+              M: MapKind<K, Vec<V>>,
+    {
+        group_map::into_group_map_in(self, kind)
+    }
+
+    /// Return a map of the given kind, of keys mapped to `Vec`s of values. The
+    /// key is specified in the closure.
+    ///
+    /// This is like [`.into_group_map_by()`](Itertools::into_group_map_by), but
+    /// collects in maps of any [`MapKind`](crate::traits::MapKind).
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let lookup = vec!["apple", "cherry", "avocado", "banana"].into_iter()
+    ///     .into_group_map_by_in(BTreeMapKind, |word| word.chars().next().unwrap());
+    ///
+    /// itertools::assert_equal(lookup, vec![
+    ///     ('a', vec!["apple", "avocado"]),
+    ///     ('b', vec!["banana"]),
+    ///     ('c', vec!["cherry"]),
+    /// ]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_group_map_by_in<K, V, M, F>(self, kind: M, f: F) -> M::Map
+        where
+            Self: Iterator<Item=V> + Sized,
+            M: MapKind<K, Vec<V>>,
+            F: Fn(&V) -> K,
+    {
+        group_map::into_group_map_by_in(self, kind, f)
+    }
+
+    /// Return nested `HashMap`s, one level for each of the keying functions
+    /// in the tuple `keys`, of keys mapped to `Vec`s of values at the
+    /// innermost level.
+    ///
+    /// The maps are all built in a single pass. `keys` holds two to four
+    /// keying functions; closures need the type of their argument spelled out,
+    /// since it cannot be inferred through the tuple.
+    ///
+    /// See also [`GroupingMap::then_by`](crate::structs::GroupingMap::then_by)
+    /// to aggregate the innermost groups rather than collect them.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// type Sale = (&'static str, &'static str, u32);
+    ///
+    /// let sales = vec![("north", "apple", 3), ("south", "apple", 5), ("north", "pear", 2),
+    ///                  ("north", "apple", 4), ("south", "plum", 1)];
+    /// let lookup = sales.into_iter().into_nested_group_map_by((
+    ///     |&(region, _, _): &Sale| region,
+    ///     |&(_, product, _): &Sale| product,
+    /// ));
+    ///
+    /// assert_eq!(lookup["north"]["apple"], vec![("north", "apple", 3), ("north", "apple", 4)]);
+    /// assert_eq!(lookup["north"]["pear"], vec![("north", "pear", 2)]);
+    /// assert_eq!(lookup["south"]["apple"], vec![("south", "apple", 5)]);
+    /// assert_eq!(lookup["south"]["plum"], vec![("south", "plum", 1)]);
+    /// assert_eq!(lookup.len(), 2);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn into_nested_group_map_by<G>(self, keys: G)
+        -> <G::Kind as MapKind<G::Key, Vec<Self::Item>>>::Map
+        where Self: Sized,
+              G: NestedGroupKeys<Self::Item, HashMapKind>,
+              G::Kind: MapKind<G::Key, Vec<Self::Item>>,
+    {
+        group_map::into_nested_group_map_by_in(self, HashMapKind::default(), keys)
+    }
+
+    /// Return nested maps of the given kind, one level for each of the keying
+    /// functions in the tuple `keys`, of keys mapped to `Vec`s of values at the
+    /// innermost level.
+    ///
+    /// This is like
+    /// [`.into_nested_group_map_by()`](Itertools::into_nested_group_map_by),
+    /// but collects in maps of any [`MapKind`](crate::traits::MapKind).
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let lookup = (0..12).into_nested_group_map_by_in(BTreeMapKind, (
+    ///     |&n: &u32| n % 2,
+    ///     |&n: &u32| n % 3,
+    ///     |&n: &u32| n < 6,
+    /// ));
+    ///
+    /// assert_eq!(lookup[&0][&0][&true], vec![0]);
+    /// assert_eq!(lookup[&0][&0][&false], vec![6]);
+    /// assert_eq!(lookup[&1][&2][&false], vec![11]);
+    /// itertools::assert_equal(lookup[&1].keys(), &[0, 1, 2]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_nested_group_map_by_in<M, G>(self, kind: M, keys: G)
+        -> <G::Kind as MapKind<G::Key, Vec<Self::Item>>>::Map
+        where Self: Sized,
+              G: NestedGroupKeys<Self::Item, M>,
+              G::Kind: MapKind<G::Key, Vec<Self::Item>>,
+    {
+        group_map::into_nested_group_map_by_in(self, kind, keys)
+    }
+
     /// Constructs a `GroupingMap` to be used later with one of the efficient 
     /// group-and-fold operations it allows to perform.
     /// 
@@ -2585,6 +3721,58 @@ pub trait Itertools : Iterator {
         grouping_map::new(grouping_map::MapForGrouping::new(self, key_mapper))
     }
 
+    /// Constructs a `GroupingMap` that collects in maps of the given kind, to
+    /// be used later with one of the efficient group-and-fold operations it
+    /// allows to perform.
+    ///
+    /// This is like [`.into_grouping_map()`](Itertools::into_grouping_map),
+    /// but the results can be any [`MapKind`](crate::traits::MapKind), such as
+    /// `BTreeMap`s to get the keys in order, or `HashMap`s with another hasher.
+    /// Without `std`, this is the only way to get a `GroupingMap`.
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind, HashMapKind};
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let data = vec![(1, 2), (0, 3), (1, 4), (2, 5), (0, 6)];
+    /// let lookup = data.iter().copied().into_grouping_map_in(BTreeMapKind).sum();
+    /// itertools::assert_equal(lookup, vec![(0, 9), (1, 6), (2, 5)]);
+    ///
+    /// let kind = HashMapKind::with_hasher(RandomState::new());
+    /// let lookup = data.into_iter().into_grouping_map_in(kind).max();
+    /// assert_eq!(lookup[&0], 6);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_grouping_map_in<K, V, M>(self, kind: M) -> GroupingMap<Self, M>
+        where Self: Iterator<Item=(K, V)> + Sized,
+    {
+        grouping_map::new_in(self, kind)
+    }
+
+    /// Constructs a `GroupingMap` that collects in maps of the given kind, to
+    /// be used later with one of the efficient group-and-fold operations it
+    /// allows to perform.
+    ///
+    /// This is like [`.into_grouping_map_by()`](Itertools::into_grouping_map_by),
+    /// but the results can be any [`MapKind`](crate::traits::MapKind).
+    ///
+    /// ```
+    /// use itertools::{Itertools, BTreeMapKind};
+    ///
+    /// let lookup = (1..=7)
+    ///     .into_grouping_map_by_in(BTreeMapKind, |&n| n % 3)
+    ///     .fold(0, |acc, _key, val| acc + val);
+    ///
+    /// itertools::assert_equal(lookup, vec![(0, 3 + 6), (1, 1 + 4 + 7), (2, 2 + 5)]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_grouping_map_by_in<K, V, M, F>(self, kind: M, key_mapper: F) -> GroupingMapBy<Self, F, M>
+        where Self: Iterator<Item=V> + Sized,
+              F: FnMut(&V) -> K
+    {
+        grouping_map::new_in(grouping_map::MapForGrouping::new(self, key_mapper), kind)
+    }
+
     /// Return the minimum and maximum elements in the iterator.
     ///
     /// The return type `MinMaxResult` is an enum of three variants:
@@ -3048,6 +4236,103 @@ pub trait Itertools : Iterator {
         self.for_each(|item| *counts.entry(item).or_default() += 1);
         counts
     }
+
+    /// Collect the items in this iterator and return a `HashMap` which
+    /// contains each key that `f` maps the items to and the number of items
+    /// that it maps to that key.
+    ///
+    /// # Examples
+    /// ```
+    /// # use itertools::Itertools;
+    /// let words = ["apple", "avocado", "banana", "cherry", "blueberry"];
+    /// let counts = words.iter().counts_by(|word| word.chars().next());
+    /// assert_eq!(counts[&Some('a')], 2);
+    /// assert_eq!(counts[&Some('b')], 2);
+    /// assert_eq!(counts[&Some('c')], 1);
+    /// assert_eq!(counts.get(&Some('d')), None);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn counts_by<K, F>(self, f: F) -> HashMap<K, usize>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+        F: FnMut(Self::Item) -> K,
+    {
+        self.map(f).counts()
+    }
+
+    /// Collect the items in this iterator and return a map of the given kind
+    /// which contains each item that appears in the iterator and the number
+    /// of times it appears.
+    ///
+    /// This is like [`.counts()`](Itertools::counts), but the counts can be
+    /// collected in any [`MapKind`](crate::traits::MapKind), such as
+    /// [`BTreeMapKind`](crate::BTreeMapKind) to get the items in order, which
+    /// also counts without `std`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use itertools::{Itertools, BTreeMapKind};
+    /// let counts = [5, 1, 3, 1, 3, 1].iter().counts_in(BTreeMapKind);
+    /// itertools::assert_equal(counts, vec![(&1, 3), (&3, 2), (&5, 1)]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn counts_in<M>(self, kind: M) -> M::Map
+    where
+        Self: Sized,
+        M: MapKind<Self::Item, usize>,
+    {
+        let mut counts = kind.new_map();
+        self.for_each(|item| *kind.entry_or_insert_with(&mut counts, item, || 0) += 1);
+        counts
+    }
+
+    /// Count the items in this iterator into a [`Counts`] frequency table,
+    /// which finds the most and least common items, and the total count.
+    ///
+    /// # Examples
+    /// ```
+    /// # use itertools::Itertools;
+    /// let words = ["the", "cat", "saw", "the", "other", "cat", "and", "the", "dog"];
+    /// let counts = words.iter().into_counts();
+    /// assert_eq!(counts.most_common(2), vec![(&&"the", 3), (&&"cat", 2)]);
+    /// assert_eq!(counts.total(), 9);
+    /// assert_eq!(counts.len(), 6);
+    /// ```
+    #[cfg(feature = "use_std")]
+    fn into_counts(self) -> Counts<Self::Item, HashMapKind>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        self.collect()
+    }
+
+    /// Count the items in this iterator into a [`Counts`] frequency table that
+    /// keeps them in a map of the given kind.
+    ///
+    /// With [`BTreeMapKind`](crate::BTreeMapKind), this makes an
+    /// [`OrderedCounts`], whose items with equal counts come in order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use itertools::{Itertools, BTreeMapKind};
+    /// let mut counts = [3, 1, 2, 3, 1, 3].iter().into_counts_in(BTreeMapKind);
+    /// counts.merge([2, 2, 4].iter().into_counts_in(BTreeMapKind));
+    /// assert_eq!(counts.most_common(2), vec![(&&2, 3), (&&3, 3)]);
+    /// assert_eq!(counts.least_common(1), vec![(&&4, 1)]);
+    /// itertools::assert_equal(counts.into_map(), vec![(&1, 2), (&2, 3), (&3, 3), (&4, 1)]);
+    /// ```
+    #[cfg(feature = "use_alloc")]
+    fn into_counts_in<M>(self, kind: M) -> Counts<Self::Item, M>
+    where
+        Self: Sized,
+        M: MapKind<Self::Item, usize>,
+    {
+        let mut counts = Counts::new_in(kind);
+        counts.extend(self);
+        counts
+    }
 }
 
 impl<T: ?Sized> Itertools for T where T: Iterator { }
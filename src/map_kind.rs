@@ -0,0 +1,405 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "use_std")]
+use std::collections::hash_map::{HashMap, RandomState};
+#[cfg(feature = "use_std")]
+use std::hash::{BuildHasher, Hash};
+
+#[cfg(feature = "use_std")]
+use crate::ordered_map::OrderedMapKind;
+
+/// A kind of map, such as `HashMap` or `BTreeMap`, that grouping operations
+/// collect their results in.
+///
+/// Each grouping operation needs maps from the same keys `K` to different kinds
+/// of values `V`, so this is implemented by a marker type like [`HashMapKind`]
+/// or [`BTreeMapKind`] for all the `V`s at once, rather than by a map type.
+///
+/// See [`.into_grouping_map_in()`](crate::Itertools::into_grouping_map_in)
+/// and [`.into_group_map_in()`](crate::Itertools::into_group_map_in).
+///
+/// Without `std`, the methods that collect in `HashMap`s, such as `.counts()`,
+/// `.unique()`, `.into_group_map()` and `.into_grouping_map()`, are not
+/// available: their `_in` counterparts with [`BTreeMapKind`] take their place.
+/// They do not fall back to `BTreeMap`s under the same names, because cargo
+/// features are additive: another crate of the build enabling `use_std` would
+/// turn their results into `HashMap`s and their `Ord` bounds into `Hash` ones,
+/// and break the `no_std` code calling them.
+pub trait MapKind<K, V> {
+    /// The type of the maps.
+    type Map;
+
+    /// Creates an empty map.
+    fn new_map(&self) -> Self::Map;
+
+    /// Returns the number of keys in `map`.
+    fn len(&self, map: &Self::Map) -> usize;
+
+    /// Returns a reference to the value of `key` in `map`, if it is there.
+    fn get<'a>(&self, map: &'a Self::Map, key: &K) -> Option<&'a V>
+        where Self: 'a, K: 'a, V: 'a;
+
+    /// Returns a mutable reference to the value of `key` in `map`, if it is
+    /// there.
+    fn get_mut<'a>(&self, map: &'a mut Self::Map, key: &K) -> Option<&'a mut V>
+        where Self: 'a, K: 'a, V: 'a;
+
+    /// Removes `key` from `map`, returning its value if it was there.
+    fn remove(&self, map: &mut Self::Map, key: &K) -> Option<V>;
+
+    /// Inserts `value` for `key` into `map`, returning the previous value if
+    /// there was one.
+    fn insert(&self, map: &mut Self::Map, key: K, value: V) -> Option<V>;
+
+    /// Returns the value of `key` in `map`, inserting `default()` first if
+    /// `key` is not there.
+    fn entry_or_insert_with<'a, F>(&self, map: &'a mut Self::Map, key: K, default: F) -> &'a mut V
+        where F: FnOnce() -> V,
+              Self: 'a, K: 'a, V: 'a;
+
+    /// Calls `f` on a reference to each value of `map`.
+    fn for_each_value<F>(&self, map: &Self::Map, f: F)
+        where F: FnMut(&V);
+
+    /// Calls `f` on a mutable reference to each value of `map`.
+    fn for_each_value_mut<F>(&self, map: &mut Self::Map, f: F)
+        where F: FnMut(&mut V);
+
+    /// Replaces the value of `key` in `map`, if any, by the value `f(key, value)`
+    /// returns, and removes `key` if that is `None`.
+    ///
+    /// `f` is called exactly once, and gives `key` back so that it can go into
+    /// the map, or be split between the levels of nested maps.
+    fn update<F>(&self, map: &mut Self::Map, key: K, f: F)
+        where F: FnOnce(K, Option<V>) -> (K, Option<V>),
+    {
+        let value = self.remove(map, &key);
+        if let (key, Some(value)) = f(key, value) {
+            self.insert(map, key, value);
+        }
+    }
+}
+
+/// Collects grouping results in `HashMap`s built with a hasher of type `S`,
+/// `RandomState` by default.
+///
+/// The order of the keys in the maps is unspecified.
+#[cfg(feature = "use_std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HashMapKind<S = RandomState> {
+    hasher: S,
+}
+
+#[cfg(feature = "use_std")]
+impl<S> HashMapKind<S> {
+    /// Collects into `HashMap`s using clones of `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        HashMapKind { hasher }
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl<K, V, S> MapKind<K, V> for HashMapKind<S>
+    where K: Hash + Eq,
+          S: BuildHasher + Clone,
+{
+    type Map = HashMap<K, V, S>;
+
+    fn new_map(&self) -> Self::Map {
+        HashMap::with_hasher(self.hasher.clone())
+    }
+
+    fn len(&self, map: &Self::Map) -> usize {
+        map.len()
+    }
+
+    fn get<'a>(&self, map: &'a Self::Map, key: &K) -> Option<&'a V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get(key)
+    }
+
+    fn get_mut<'a>(&self, map: &'a mut Self::Map, key: &K) -> Option<&'a mut V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get_mut(key)
+    }
+
+    fn remove(&self, map: &mut Self::Map, key: &K) -> Option<V> {
+        map.remove(key)
+    }
+
+    fn insert(&self, map: &mut Self::Map, key: K, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    fn entry_or_insert_with<'a, F>(&self, map: &'a mut Self::Map, key: K, default: F) -> &'a mut V
+        where F: FnOnce() -> V,
+              Self: 'a, K: 'a, V: 'a,
+    {
+        map.entry(key).or_insert_with(default)
+    }
+
+    fn for_each_value<F>(&self, map: &Self::Map, f: F)
+        where F: FnMut(&V)
+    {
+        map.values().for_each(f)
+    }
+
+    fn for_each_value_mut<F>(&self, map: &mut Self::Map, f: F)
+        where F: FnMut(&mut V)
+    {
+        map.values_mut().for_each(f)
+    }
+}
+
+/// Collects grouping results in `BTreeMap`s, which iterate in key order.
+///
+/// This only needs keys to be `Ord`, and is available without `std`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BTreeMapKind;
+
+impl<K, V> MapKind<K, V> for BTreeMapKind
+    where K: Ord,
+{
+    type Map = BTreeMap<K, V>;
+
+    fn new_map(&self) -> Self::Map {
+        BTreeMap::new()
+    }
+
+    fn len(&self, map: &Self::Map) -> usize {
+        map.len()
+    }
+
+    fn get<'a>(&self, map: &'a Self::Map, key: &K) -> Option<&'a V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get(key)
+    }
+
+    fn get_mut<'a>(&self, map: &'a mut Self::Map, key: &K) -> Option<&'a mut V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get_mut(key)
+    }
+
+    fn remove(&self, map: &mut Self::Map, key: &K) -> Option<V> {
+        map.remove(key)
+    }
+
+    fn insert(&self, map: &mut Self::Map, key: K, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    fn entry_or_insert_with<'a, F>(&self, map: &'a mut Self::Map, key: K, default: F) -> &'a mut V
+        where F: FnOnce() -> V,
+              Self: 'a, K: 'a, V: 'a,
+    {
+        map.entry(key).or_insert_with(default)
+    }
+
+    fn for_each_value<F>(&self, map: &Self::Map, f: F)
+        where F: FnMut(&V)
+    {
+        map.values().for_each(f)
+    }
+
+    fn for_each_value_mut<F>(&self, map: &mut Self::Map, f: F)
+        where F: FnMut(&mut V)
+    {
+        map.values_mut().for_each(f)
+    }
+}
+
+/// Collects grouping results in two levels of maps, keyed by pairs
+/// `(outer, inner)`: maps of kind `N` from the outer keys to maps of kind `B`
+/// from the inner keys.
+///
+/// The outer maps only hold the outer keys that have inner keys.
+///
+/// See [`GroupingMap::then_by`](crate::structs::GroupingMap::then_by).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NestedMapKind<N, B> {
+    outer: N,
+    inner: B,
+}
+
+impl<N, B> NestedMapKind<N, B> {
+    /// Nests maps of kind `inner` in maps of kind `outer`.
+    pub fn new(outer: N, inner: B) -> Self {
+        NestedMapKind { outer, inner }
+    }
+}
+
+impl<K1, K2, V, N, B> MapKind<(K1, K2), V> for NestedMapKind<N, B>
+    where B: MapKind<K2, V>,
+          N: MapKind<K1, B::Map>,
+{
+    type Map = N::Map;
+
+    fn new_map(&self) -> Self::Map {
+        self.outer.new_map()
+    }
+
+    fn len(&self, map: &Self::Map) -> usize {
+        let mut len = 0;
+        self.outer.for_each_value(map, |inner| len += self.inner.len(inner));
+        len
+    }
+
+    fn get<'a>(&self, map: &'a Self::Map, key: &(K1, K2)) -> Option<&'a V>
+        where Self: 'a, (K1, K2): 'a, V: 'a
+    {
+        let inner = self.outer.get(map, &key.0)?;
+        self.inner.get(inner, &key.1)
+    }
+
+    fn get_mut<'a>(&self, map: &'a mut Self::Map, key: &(K1, K2)) -> Option<&'a mut V>
+        where Self: 'a, (K1, K2): 'a, V: 'a
+    {
+        let inner = self.outer.get_mut(map, &key.0)?;
+        self.inner.get_mut(inner, &key.1)
+    }
+
+    fn remove(&self, map: &mut Self::Map, key: &(K1, K2)) -> Option<V> {
+        let inner = self.outer.get_mut(map, &key.0)?;
+        let value = self.inner.remove(inner, &key.1);
+        if self.inner.len(inner) == 0 {
+            self.outer.remove(map, &key.0);
+        }
+        value
+    }
+
+    fn insert(&self, map: &mut Self::Map, key: (K1, K2), value: V) -> Option<V> {
+        let inner = self.outer.entry_or_insert_with(map, key.0, || self.inner.new_map());
+        self.inner.insert(inner, key.1, value)
+    }
+
+    fn entry_or_insert_with<'a, F>(&self, map: &'a mut Self::Map, key: (K1, K2), default: F) -> &'a mut V
+        where F: FnOnce() -> V,
+              Self: 'a, (K1, K2): 'a, V: 'a,
+    {
+        let inner = self.outer.entry_or_insert_with(map, key.0, || self.inner.new_map());
+        self.inner.entry_or_insert_with(inner, key.1, default)
+    }
+
+    fn for_each_value<F>(&self, map: &Self::Map, mut f: F)
+        where F: FnMut(&V)
+    {
+        self.outer.for_each_value(map, |inner| self.inner.for_each_value(inner, &mut f))
+    }
+
+    fn for_each_value_mut<F>(&self, map: &mut Self::Map, mut f: F)
+        where F: FnMut(&mut V)
+    {
+        self.outer.for_each_value_mut(map, |inner| self.inner.for_each_value_mut(inner, &mut f))
+    }
+
+    fn update<F>(&self, map: &mut Self::Map, key: (K1, K2), f: F)
+        where F: FnOnce((K1, K2), Option<V>) -> ((K1, K2), Option<V>),
+    {
+        let (k1, k2) = key;
+        // Update the value through the inner kind, which may keep its key in
+        // place, and keep the inner map in place while its only key is updated.
+        let emptied = match self.outer.get_mut(map, &k1) {
+            Some(inner) => {
+                let mut outer_key = None;
+                self.inner.update(inner, k2, |k2, value| {
+                    let ((k1, k2), value) = f((k1, k2), value);
+                    outer_key = Some(k1);
+                    (k2, value)
+                });
+                outer_key.filter(|_| self.inner.len(inner) == 0)
+            }
+            None => {
+                if let ((k1, k2), Some(value)) = f((k1, k2), None) {
+                    self.insert(map, (k1, k2), value);
+                }
+                None
+            }
+        };
+        if let Some(k1) = emptied {
+            self.outer.remove(map, &k1);
+        }
+    }
+}
+
+/// A map kind that
+/// [`GroupingMap::then_by`](crate::structs::GroupingMap::then_by) can nest
+/// another level of maps in.
+pub trait NestableMapKind {
+    /// The kind of the maps of the innermost level, which the new level uses
+    /// too.
+    type Innermost: Clone;
+
+    /// Returns the kind of the maps of the innermost level.
+    fn innermost(&self) -> Self::Innermost;
+}
+
+#[cfg(feature = "use_std")]
+impl<S: Clone> NestableMapKind for HashMapKind<S> {
+    type Innermost = Self;
+
+    fn innermost(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl NestableMapKind for BTreeMapKind {
+    type Innermost = Self;
+
+    fn innermost(&self) -> Self {
+        *self
+    }
+}
+
+impl<N, B: NestableMapKind> NestableMapKind for NestedMapKind<N, B> {
+    type Innermost = B::Innermost;
+
+    fn innermost(&self) -> Self::Innermost {
+        self.inner.innermost()
+    }
+}
+
+/// A map kind that
+/// [`GroupingMap::ordered`](crate::structs::GroupingMap::ordered) can turn
+/// into one of [`OrderedMap`](crate::OrderedMap)s, with the same levels of
+/// maps and the same hasher.
+#[cfg(feature = "use_std")]
+pub trait OrderableMapKind {
+    /// The kind of `OrderedMap`s that replaces this one.
+    type Ordered;
+
+    /// Returns the kind of `OrderedMap`s that replaces this one.
+    fn ordered(self) -> Self::Ordered;
+}
+
+#[cfg(feature = "use_std")]
+impl<S> OrderableMapKind for HashMapKind<S> {
+    type Ordered = OrderedMapKind<S>;
+
+    fn ordered(self) -> Self::Ordered {
+        OrderedMapKind::with_hasher(self.hasher)
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl OrderableMapKind for BTreeMapKind {
+    type Ordered = OrderedMapKind;
+
+    fn ordered(self) -> Self::Ordered {
+        OrderedMapKind::default()
+    }
+}
+
+#[cfg(feature = "use_std")]
+impl<N, B> OrderableMapKind for NestedMapKind<N, B>
+    where N: OrderableMapKind,
+          B: OrderableMapKind,
+{
+    type Ordered = NestedMapKind<N::Ordered, B::Ordered>;
+
+    fn ordered(self) -> Self::Ordered {
+        NestedMapKind::new(self.outer.ordered(), self.inner.ordered())
+    }
+}
@@ -0,0 +1,213 @@
+use alloc::vec::Vec;
+use std::fmt;
+
+use super::combinations::checked_binomial;
+
+/// An iterator adaptor that iterates through the distinct `k`-permutations of
+/// the elements from an iterator, treating equal elements as interchangeable.
+///
+/// See [`.multiset_permutations()`](crate::Itertools::multiset_permutations)
+/// for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultisetPermutations<T> {
+    // The distinct values, in the order permutations are generated in.
+    values: Vec<T>,
+    // How many copies of each value are not used by the current permutation.
+    avail: Vec<usize>,
+    // The current permutation, as indices into `values`.
+    indices: Vec<usize>,
+    first: bool,
+    done: bool,
+    // Permutations left to yield, `None` if that overflows `usize`.
+    remaining: Option<usize>,
+}
+
+impl<T> Clone for MultisetPermutations<T>
+    where T: Clone,
+{
+    clone_fields!(values, avail, indices, first, done, remaining);
+}
+
+impl<T> fmt::Debug for MultisetPermutations<T>
+    where T: fmt::Debug,
+{
+    debug_fmt_fields!(MultisetPermutations, values, avail, indices, first, done, remaining);
+}
+
+/// Create a new `MultisetPermutations` that yields permutations in lexicographic
+/// order of the elements.
+pub fn multiset_permutations<I>(iter: I, k: usize) -> MultisetPermutations<I::Item>
+    where I: Iterator,
+          I::Item: Ord,
+{
+    let mut sorted: Vec<I::Item> = iter.collect();
+    sorted.sort();
+
+    let mut values: Vec<I::Item> = Vec::new();
+    let mut counts = Vec::new();
+    for item in sorted {
+        match values.last() {
+            Some(last) if *last == item => *counts.last_mut().unwrap() += 1,
+            _ => {
+                values.push(item);
+                counts.push(1);
+            }
+        }
+    }
+
+    from_counts(values, counts, k)
+}
+
+/// Create a new `MultisetPermutations` that yields permutations in lexicographic
+/// order of the elements' first appearance in the source.
+#[cfg(feature = "use_std")]
+pub fn multiset_permutations_hash<I>(iter: I, k: usize) -> MultisetPermutations<I::Item>
+    where I: Iterator,
+          I::Item: Eq + std::hash::Hash,
+{
+    use std::collections::HashMap;
+    use std::collections::hash_map::Entry;
+
+    let mut positions = HashMap::new();
+    let mut counts = Vec::new();
+    for item in iter {
+        match positions.entry(item) {
+            Entry::Occupied(entry) => counts[*entry.get()] += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(counts.len());
+                counts.push(1);
+            }
+        }
+    }
+
+    let mut values: Vec<_> = positions.into_iter().collect();
+    values.sort_by_key(|&(_, position)| position);
+    let values = values.into_iter().map(|(value, _)| value).collect();
+
+    from_counts(values, counts, k)
+}
+
+fn from_counts<T>(values: Vec<T>, counts: Vec<usize>, k: usize) -> MultisetPermutations<T> {
+    let remaining = count_arrangements(&counts, k);
+    let mut perms = MultisetPermutations {
+        values,
+        avail: counts,
+        indices: Vec::with_capacity(k),
+        first: true,
+        done: false,
+        remaining,
+    };
+
+    // Start from the smallest permutation
+    perms.done = !perms.fill(k);
+    perms
+}
+
+/// Counts the sequences of length `k` that can be drawn from a multiset with the
+/// given multiplicities, or `None` on overflow.
+///
+/// For `k` equal to the size of the multiset this is the multinomial coefficient
+/// `n! / (c1! * c2! * ...)`; otherwise the sequences are built up one distinct value
+/// at a time, choosing the positions of its copies with a binomial coefficient.
+fn count_arrangements(counts: &[usize], k: usize) -> Option<usize> {
+    // `ways[j]` is the number of sequences of length `j` using the values seen so far.
+    let mut ways = alloc::vec![0; k + 1];
+    ways[0] = 1;
+
+    for &count in counts {
+        for j in (1..k + 1).rev() {
+            let mut total = ways[j];
+            for copies in 1..=count.min(j) {
+                let placed = checked_binomial(j, copies)?.checked_mul(ways[j - copies])?;
+                total = total.checked_add(placed)?;
+            }
+            ways[j] = total;
+        }
+    }
+
+    Some(ways[k])
+}
+
+impl<T> MultisetPermutations<T> {
+    /// Pushes the smallest available values until the permutation has length `k`.
+    /// Returns `false` if there are not enough values left.
+    fn fill(&mut self, k: usize) -> bool {
+        let mut value = 0;
+        while self.indices.len() < k {
+            while value < self.avail.len() && self.avail[value] == 0 {
+                value += 1;
+            }
+            if value == self.avail.len() {
+                return false;
+            }
+            self.avail[value] -= 1;
+            self.indices.push(value);
+        }
+        true
+    }
+
+    /// Moves to the next permutation in lexicographic order. Returns `false` if
+    /// the current one was the last.
+    fn advance(&mut self) -> bool {
+        let k = self.indices.len();
+
+        // Scan from the end, looking for a position that can take a larger value
+        while let Some(old) = self.indices.pop() {
+            self.avail[old] += 1;
+
+            let larger = (old + 1..self.avail.len()).find(|&v| self.avail[v] > 0);
+            if let Some(value) = larger {
+                self.avail[value] -= 1;
+                self.indices.push(value);
+                // Reset the positions to its right to the smallest arrangement
+                return self.fill(k);
+            }
+        }
+
+        false
+    }
+}
+
+impl<T> Iterator for MultisetPermutations<T>
+    where T: Clone,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.first {
+            self.first = false;
+        } else if !self.advance() {
+            self.done = true;
+            return None;
+        }
+
+        self.remaining = self.remaining.map(|r| r - 1);
+        Some(self.indices.iter().map(|&i| self.values[i].clone()).collect())
+    }
+
+    fn count(self) -> usize {
+        if self.done {
+            return 0;
+        }
+
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use alloc::vec::Vec;
+use std::fmt;
+
+use super::lazy_buffer::LazyBuffer;
+use super::size_hint;
+
+/// An iterator adaptor that iterates through the necklaces or bracelets of
+/// length `k` over the elements from an iterator.
+///
+/// See [`.necklaces()`](crate::Itertools::necklaces) and
+/// [`.bracelets()`](crate::Itertools::bracelets) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Necklaces<I: Iterator> {
+    pool: LazyBuffer<I>,
+    k: usize,
+    // Whether to also skip sequences equal up to reflection.
+    bracelets: bool,
+    // The current necklace, as positions into the pool.
+    indices: Vec<usize>,
+    first: bool,
+    done: bool,
+    // Necklaces left to yield once the pool is complete, `None` on overflow.
+    remaining: Option<usize>,
+}
+
+impl<I> Clone for Necklaces<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(pool, k, bracelets, indices, first, done, remaining);
+}
+
+impl<I> fmt::Debug for Necklaces<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(Necklaces, pool, k, bracelets, indices, first, done, remaining);
+}
+
+/// Create a new `Necklaces` yielding sequences distinct up to rotation.
+pub fn necklaces<I>(iter: I, k: usize) -> Necklaces<I>
+    where I: Iterator,
+{
+    new(iter, k, false)
+}
+
+/// Create a new `Necklaces` yielding sequences distinct up to rotation and
+/// reflection.
+pub fn bracelets<I>(iter: I, k: usize) -> Necklaces<I>
+    where I: Iterator,
+{
+    new(iter, k, true)
+}
+
+fn new<I>(iter: I, k: usize, bracelets: bool) -> Necklaces<I>
+    where I: Iterator,
+{
+    Necklaces {
+        pool: LazyBuffer::new(iter),
+        k,
+        bracelets,
+        indices: Vec::new(),
+        first: true,
+        done: false,
+        remaining: None,
+    }
+}
+
+impl<I: Iterator> Necklaces<I> {
+    /// Reads the whole source and moves to the first necklace.
+    fn start(&mut self) {
+        self.first = false;
+        self.pool.prefill(usize::MAX);
+
+        let m = self.pool.len();
+        self.remaining = count_necklaces(m, self.k, self.bracelets);
+        // Repeating the first element is the smallest necklace and bracelet.
+        self.indices = alloc::vec![0; self.k];
+        self.done = m == 0 && self.k > 0;
+    }
+
+    /// Moves to the next necklace in lexicographic order. Returns `false` if the
+    /// current one was the last.
+    ///
+    /// This is the algorithm of Fredricksen, Kessler and Maiorana: it steps
+    /// through the prenecklaces, the prefixes of necklaces, and keeps those whose
+    /// period divides `k`.
+    fn advance(&mut self) -> bool {
+        let (m, k) = (self.pool.len(), self.k);
+        loop {
+            // Scan from the end, looking for a position that can take a larger element
+            let i = match self.indices.iter().rposition(|&index| index + 1 < m) {
+                Some(i) => i,
+                None => return false,
+            };
+
+            // Repeat the new prefix up to length `k`
+            self.indices[i] += 1;
+            for j in i + 1..k {
+                self.indices[j] = self.indices[j - i - 1];
+            }
+
+            if k % (i + 1) == 0 && (!self.bracelets || self.is_bracelet()) {
+                return true;
+            }
+        }
+    }
+
+    /// Returns whether the current necklace is no larger than any rotation of
+    /// its reversal, i.e. the smallest of its bracelet.
+    fn is_bracelet(&self) -> bool {
+        let k = self.k;
+        (0..k).all(|shift| {
+            let reversed = (0..k).map(|j| self.indices[(k + shift - j) % k]);
+            self.indices.iter().copied().le(reversed)
+        })
+    }
+}
+
+impl<I> Iterator for Necklaces<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.start();
+        } else if !self.done && !self.advance() {
+            self.done = true;
+        }
+
+        if self.done {
+            return None;
+        }
+
+        self.remaining = self.remaining.map(|r| r - 1);
+        Some(self.indices.iter().map(|&i| self.pool[i].clone()).collect())
+    }
+
+    fn count(mut self) -> usize {
+        if self.first {
+            self.start();
+        }
+
+        if self.done {
+            return 0;
+        }
+
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        if self.first {
+            let (low, high) = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+            let low = count_necklaces(low, self.k, self.bracelets).unwrap_or(usize::MAX);
+            let high = high.and_then(|m| count_necklaces(m, self.k, self.bracelets));
+            return (low, high);
+        }
+
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Counts the necklaces (or bracelets) of length `k` over `m` elements, or
+/// `None` on overflow.
+///
+/// By Burnside's lemma, there are `sum(phi(d) * m^(k / d)) / k` necklaces, the
+/// sum running over the divisors `d` of `k`. Bracelets also identify each
+/// necklace with its reflection, which adds `m^((k + 1) / 2)` fixed sequences
+/// for odd `k`, and `(m + 1) * m^(k / 2) / 2` for even `k`, before halving.
+fn count_necklaces(m: usize, k: usize, bracelets: bool) -> Option<usize> {
+    if k == 0 || m <= 1 {
+        // Only the empty sequence, or a single repeated element
+        return Some(if k == 0 || m == 1 { 1 } else { 0 });
+    }
+    if k >= u128::BITS as usize {
+        return None;
+    }
+
+    let m = m as u128;
+    let k32 = k as u32;
+    let mut sum: u128 = 0;
+    // Only the divisors of `k` take part
+    for d in (1..=k).filter(|&d| k / d * d == k) {
+        let term = m.checked_pow(k32 / d as u32)?.checked_mul(totient(d) as u128)?;
+        sum = sum.checked_add(term)?;
+    }
+    let mut count = sum / k as u128;
+
+    if bracelets {
+        let reflected = if k % 2 == 1 {
+            m.checked_pow(k32 / 2 + 1)?
+        } else {
+            (m + 1).checked_mul(m.checked_pow(k32 / 2)?)? / 2
+        };
+        count = count.checked_add(reflected)? / 2;
+    }
+
+    if count > usize::MAX as u128 {
+        return None;
+    }
+    Some(count as usize)
+}
+
+/// Counts the integers in `1..=n` that are coprime to `n`.
+fn totient(n: usize) -> usize {
+    let (mut n, mut phi) = (n, n);
+    let mut p = 2;
+    while p * p <= n {
+        if n % p == 0 {
+            while n % p == 0 {
+                n /= p;
+            }
+            phi -= phi / p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        phi -= phi / n;
+    }
+    phi
+}
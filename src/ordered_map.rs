@@ -0,0 +1,546 @@
+//! A map that keeps its keys in the order they were first inserted, for
+//! [`.into_ordered_group_map()`](crate::Itertools::into_ordered_group_map)
+//! and [`GroupingMap::ordered`](crate::structs::GroupingMap::ordered).
+//!
+//! ```
+//! use itertools::Itertools;
+//!
+//! let lookup = vec!["pear", "apple", "plum", "apricot", "peach"].into_iter()
+//!     .into_ordered_group_map_by(|fruit| fruit.chars().next().unwrap());
+//!
+//! itertools::assert_equal(lookup, vec![
+//!     ('p', vec!["pear", "plum", "peach"]),
+//!     ('a', vec!["apple", "apricot"]),
+//! ]);
+//! ```
+
+use alloc::vec::{self, Vec};
+use std::collections::HashMap;
+use std::collections::hash_map::{Entry, RandomState};
+use std::fmt;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::Index;
+use std::slice;
+
+use crate::map_kind::{MapKind, NestableMapKind, OrderableMapKind};
+
+// Hashes the hashes of the keys, which already are, as themselves.
+#[derive(Clone, Copy, Debug, Default)]
+struct HashIdentity(u64);
+
+impl Hasher for HashIdentity {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+}
+
+// The indices of the entries whose keys have the same hash: nearly always one.
+#[derive(Clone, Debug)]
+enum Slot {
+    One(usize),
+    Many(Vec<usize>),
+}
+
+impl Slot {
+    fn indices(&self) -> &[usize] {
+        match self {
+            Slot::One(index) => slice::from_ref(index),
+            Slot::Many(indices) => indices,
+        }
+    }
+
+    fn indices_mut(&mut self) -> &mut [usize] {
+        match self {
+            Slot::One(index) => slice::from_mut(index),
+            Slot::Many(indices) => indices,
+        }
+    }
+}
+
+/// A map that iterates in the order its keys were first inserted.
+///
+/// The entries are kept in a `Vec`, in order, and found through a hash map of
+/// the hashes of their keys, which are hashed with a hasher of type `S`,
+/// `RandomState` by default. Finding, inserting and updating entries takes
+/// constant time on average, like with `HashMap`; removing one takes time
+/// linear in the number of entries, as the later ones move up.
+///
+/// Two maps are equal if they have the same entries in the same order.
+#[derive(Clone)]
+pub struct OrderedMap<K, V, S = RandomState> {
+    entries: Vec<(K, V)>,
+    index: HashMap<u64, Slot, BuildHasherDefault<HashIdentity>>,
+    hasher: S,
+}
+
+impl<K, V> OrderedMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        OrderedMap::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S> {
+    /// Creates an empty map, that hashes its keys with `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+            index: HashMap::default(),
+            hasher,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entry at position `index` in the order of the map.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.entries.get(index).map(|(key, value)| (key, value))
+    }
+
+    /// Returns an iterator over the entries, in order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.entries.iter() }
+    }
+
+    /// Returns an iterator over the entries, in order, with mutable
+    /// references to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut { iter: self.entries.iter_mut() }
+    }
+
+    /// Returns an iterator over the keys, in order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.entries.iter() }
+    }
+
+    /// Returns an iterator over the values, in the order of their keys.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.entries.iter() }
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S>
+    where K: Hash + Eq,
+          S: BuildHasher,
+{
+    fn hash(&self, key: &K) -> u64 {
+        let mut state = self.hasher.build_hasher();
+        Hash::hash(key, &mut state);
+        state.finish()
+    }
+
+    // Returns the hash of `key`, and its index if it is there
+    fn find(&self, key: &K) -> (u64, Option<usize>) {
+        let hash = self.hash(key);
+        let index = self.index.get(&hash).and_then(|slot| {
+            slot.indices().iter().cloned().find(|&i| self.entries[i].0 == *key)
+        });
+        (hash, index)
+    }
+
+    // Appends an entry for a key that is not there
+    fn push(&mut self, hash: u64, key: K, value: V) -> usize {
+        let index = self.entries.len();
+        self.entries.push((key, value));
+        match self.index.entry(hash) {
+            Entry::Vacant(entry) => {
+                entry.insert(Slot::One(index));
+            }
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                Slot::One(other) => {
+                    let other = *other;
+                    entry.insert(Slot::Many(alloc::vec![other, index]));
+                }
+                Slot::Many(indices) => indices.push(index),
+            },
+        }
+        index
+    }
+
+    // Forgets the entry at `index`, which has `hash`, once it is not in
+    // `entries` any more and the later ones moved up
+    fn unindex(&mut self, hash: u64, index: usize) {
+        if let Entry::Occupied(mut entry) = self.index.entry(hash) {
+            match entry.get_mut() {
+                Slot::One(_) => {
+                    entry.remove();
+                }
+                Slot::Many(indices) => {
+                    indices.retain(|&i| i != index);
+                    if let [i] = indices[..] {
+                        entry.insert(Slot::One(i));
+                    }
+                }
+            }
+        }
+        if index < self.entries.len() {
+            self.index.values_mut()
+                .flat_map(|slot| slot.indices_mut())
+                .filter(|i| **i > index)
+                .for_each(|i| *i -= 1);
+        }
+    }
+
+    /// Returns `true` if `key` is in the map.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).1.is_some()
+    }
+
+    /// Returns a reference to the value of `key`, if it is there.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).1.map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value of `key`, if it is there.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.find(key).1 {
+            Some(i) => Some(&mut self.entries[i].1),
+            None => None,
+        }
+    }
+
+    /// Inserts `value` for `key`, returning the previous value if there was
+    /// one.
+    ///
+    /// A new key goes last, and a key that is there keeps its place.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            (_, Some(i)) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            (hash, None) => {
+                self.push(hash, key, value);
+                None
+            }
+        }
+    }
+
+    /// Removes `key`, returning its value if it was there.
+    ///
+    /// The later entries move up, so this takes time linear in their number.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match self.find(key) {
+            (hash, Some(i)) => {
+                let (_, value) = self.entries.remove(i);
+                self.unindex(hash, i);
+                Some(value)
+            }
+            (_, None) => None,
+        }
+    }
+
+    fn entry_or_insert_with<F>(&mut self, key: K, default: F) -> &mut V
+        where F: FnOnce() -> V,
+    {
+        let index = match self.find(&key) {
+            (_, Some(i)) => i,
+            (hash, None) => self.push(hash, key, default()),
+        };
+        &mut self.entries[index].1
+    }
+
+    fn update<F>(&mut self, key: K, f: F)
+        where F: FnOnce(K, Option<V>) -> (K, Option<V>),
+    {
+        let (hash, index) = self.find(&key);
+        let index = match index {
+            Some(i) => i,
+            None => {
+                if let (key, Some(value)) = f(key, None) {
+                    self.push(hash, key, value);
+                }
+                return;
+            }
+        };
+        // Take the value out with the last entry in its place, and put them
+        // back in their places after
+        let last = self.entries.len() - 1;
+        let (key, value) = self.entries.swap_remove(index);
+        match f(key, Some(value)) {
+            (key, Some(value)) => {
+                self.entries.push((key, value));
+                self.entries.swap(index, last);
+            }
+            (_, None) => {
+                if index < last {
+                    self.entries[index..].rotate_left(1);
+                }
+                self.unindex(hash, index);
+            }
+        }
+    }
+}
+
+impl<K, V, S> Default for OrderedMap<K, V, S>
+    where S: Default,
+{
+    fn default() -> Self {
+        OrderedMap::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> fmt::Debug for OrderedMap<K, V, S>
+    where K: fmt::Debug,
+          V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, S> PartialEq for OrderedMap<K, V, S>
+    where K: PartialEq,
+          V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K, V, S> Eq for OrderedMap<K, V, S>
+    where K: Eq,
+          V: Eq,
+{}
+
+impl<K, V, S> Index<&K> for OrderedMap<K, V, S>
+    where K: Hash + Eq,
+          S: BuildHasher,
+{
+    type Output = V;
+
+    /// **Panics** if `key` is not in the map.
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("key not in the OrderedMap")
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for OrderedMap<K, V, S>
+    where K: Hash + Eq,
+          S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(key, value)| {
+            self.insert(key, value);
+        })
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for OrderedMap<K, V, S>
+    where K: Hash + Eq,
+          S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = OrderedMap::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> IntoIterator for OrderedMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { iter: self.entries.into_iter() }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a OrderedMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut OrderedMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+macro_rules! ordered_map_iterator {
+    ($name:ident [$($lt:lifetime)?] $item:ty, |$entry:pat| $map:expr) => {
+        impl<$($lt,)? K, V> Iterator for $name<$($lt,)? K, V> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next().map(|$entry| $map)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<$($lt,)? K, V> DoubleEndedIterator for $name<$($lt,)? K, V> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.iter.next_back().map(|$entry| $map)
+            }
+        }
+
+        impl<$($lt,)? K, V> ExactSizeIterator for $name<$($lt,)? K, V> {}
+
+        impl<$($lt,)? K, V> FusedIterator for $name<$($lt,)? K, V> {}
+    };
+}
+
+/// An iterator over the entries of an [`OrderedMap`], in order.
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+ordered_map_iterator!(Iter ['a] (&'a K, &'a V), |(key, value)| (key, value));
+
+/// An iterator over the entries of an [`OrderedMap`], in order, with mutable
+/// references to the values.
+#[derive(Debug)]
+pub struct IterMut<'a, K, V> {
+    iter: slice::IterMut<'a, (K, V)>,
+}
+
+ordered_map_iterator!(IterMut ['a] (&'a K, &'a mut V), |(key, value)| (&*key, value));
+
+/// An iterator over the keys of an [`OrderedMap`], in order.
+#[derive(Clone, Debug)]
+pub struct Keys<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+ordered_map_iterator!(Keys ['a] &'a K, |(key, _)| key);
+
+/// An iterator over the values of an [`OrderedMap`], in the order of their
+/// keys.
+#[derive(Clone, Debug)]
+pub struct Values<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+ordered_map_iterator!(Values ['a] &'a V, |(_, value)| value);
+
+/// An iterator over the entries of an [`OrderedMap`], in order, that owns
+/// them.
+#[derive(Clone, Debug)]
+pub struct IntoIter<K, V> {
+    iter: vec::IntoIter<(K, V)>,
+}
+
+ordered_map_iterator!(IntoIter [] (K, V), |entry| entry);
+
+/// Collects grouping results in [`OrderedMap`]s built with a hasher of type
+/// `S`, `RandomState` by default, which keep the keys in the order they were
+/// first seen.
+///
+/// A key that an operation removes, like
+/// [`GroupingMap::aggregate`](crate::structs::GroupingMap::aggregate) does
+/// when it discards a group's accumulator, goes last if it comes back.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedMapKind<S = RandomState> {
+    hasher: S,
+}
+
+impl<S> OrderedMapKind<S> {
+    /// Collects into `OrderedMap`s using clones of `hasher`.
+    pub fn with_hasher(hasher: S) -> Self {
+        OrderedMapKind { hasher }
+    }
+}
+
+impl<K, V, S> MapKind<K, V> for OrderedMapKind<S>
+    where K: Hash + Eq,
+          S: BuildHasher + Clone,
+{
+    type Map = OrderedMap<K, V, S>;
+
+    fn new_map(&self) -> Self::Map {
+        OrderedMap::with_hasher(self.hasher.clone())
+    }
+
+    fn len(&self, map: &Self::Map) -> usize {
+        map.len()
+    }
+
+    fn get<'a>(&self, map: &'a Self::Map, key: &K) -> Option<&'a V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get(key)
+    }
+
+    fn get_mut<'a>(&self, map: &'a mut Self::Map, key: &K) -> Option<&'a mut V>
+        where Self: 'a, K: 'a, V: 'a
+    {
+        map.get_mut(key)
+    }
+
+    fn remove(&self, map: &mut Self::Map, key: &K) -> Option<V> {
+        map.remove(key)
+    }
+
+    fn insert(&self, map: &mut Self::Map, key: K, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    fn entry_or_insert_with<'a, F>(&self, map: &'a mut Self::Map, key: K, default: F) -> &'a mut V
+        where F: FnOnce() -> V,
+              Self: 'a, K: 'a, V: 'a,
+    {
+        map.entry_or_insert_with(key, default)
+    }
+
+    fn for_each_value<F>(&self, map: &Self::Map, f: F)
+        where F: FnMut(&V)
+    {
+        map.values().for_each(f)
+    }
+
+    fn for_each_value_mut<F>(&self, map: &mut Self::Map, mut f: F)
+        where F: FnMut(&mut V)
+    {
+        map.iter_mut().for_each(|(_, value)| f(value))
+    }
+
+    fn update<F>(&self, map: &mut Self::Map, key: K, f: F)
+        where F: FnOnce(K, Option<V>) -> (K, Option<V>),
+    {
+        // Unlike `remove` then `insert`, this keeps the key in its place
+        map.update(key, f)
+    }
+}
+
+impl<S: Clone> NestableMapKind for OrderedMapKind<S> {
+    type Innermost = Self;
+
+    fn innermost(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<S> OrderableMapKind for OrderedMapKind<S> {
+    type Ordered = Self;
+
+    fn ordered(self) -> Self {
+        self
+    }
+}
@@ -0,0 +1,366 @@
+use alloc::vec::Vec;
+use std::fmt;
+
+use super::lazy_buffer::LazyBuffer;
+
+/// An iterator to iterate through all the partitions of the elements from an
+/// iterator into non-empty blocks.
+///
+/// See [`.set_partitions()`](crate::Itertools::set_partitions) and
+/// [`.set_partitions_k()`](crate::Itertools::set_partitions_k) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct SetPartitions<I: Iterator> {
+    pool: LazyBuffer<I>,
+    // The current partition as a restricted growth string: `blocks[i]` is the
+    // block of element `i`, and each block is at most one more than all before.
+    blocks: Vec<usize>,
+    // Smallest and largest (inclusive) number of blocks to yield.
+    min_blocks: usize,
+    max_blocks: usize,
+    first: bool,
+    done: bool,
+    // Partitions left to yield once the pool is complete, `None` on overflow.
+    remaining: Option<usize>,
+}
+
+impl<I> Clone for SetPartitions<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(pool, blocks, min_blocks, max_blocks, first, done, remaining);
+}
+
+impl<I> fmt::Debug for SetPartitions<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(SetPartitions, pool, blocks, min_blocks, max_blocks, first, done, remaining);
+}
+
+/// Create a new `SetPartitions` yielding partitions with any number of blocks.
+pub fn set_partitions<I>(iter: I) -> SetPartitions<I>
+    where I: Iterator,
+{
+    new(iter, 0, usize::MAX)
+}
+
+/// Create a new `SetPartitions` yielding partitions with exactly `k` blocks.
+pub fn set_partitions_k<I>(iter: I, k: usize) -> SetPartitions<I>
+    where I: Iterator,
+{
+    new(iter, k, k)
+}
+
+fn new<I>(iter: I, min_blocks: usize, max_blocks: usize) -> SetPartitions<I>
+    where I: Iterator,
+{
+    SetPartitions {
+        pool: LazyBuffer::new(iter),
+        blocks: Vec::new(),
+        min_blocks,
+        max_blocks,
+        first: true,
+        done: false,
+        remaining: None,
+    }
+}
+
+impl<I: Iterator> SetPartitions<I> {
+    /// Reads the whole source and moves to the first partition.
+    fn start(&mut self) {
+        self.first = false;
+        self.pool.prefill(usize::MAX);
+
+        let n = self.pool.len();
+        self.remaining = count_set_partitions(n, self.min_blocks, self.max_blocks);
+        self.done = !self.fill(0);
+    }
+
+    /// Fills `blocks` up to the pool length with the smallest assignment that can
+    /// still reach an allowed number of blocks. Returns `false` if there is none.
+    fn fill(&mut self, used: usize) -> bool {
+        let n = self.pool.len();
+        let mut used = used;
+        while self.blocks.len() < n {
+            // Open a new block only when the remaining elements are all needed
+            // to reach `min_blocks`.
+            let left = n - self.blocks.len();
+            if used > 0 && used + left > self.min_blocks {
+                self.blocks.push(0);
+            } else {
+                self.blocks.push(used);
+                used += 1;
+            }
+        }
+        used >= self.min_blocks && used <= self.max_blocks
+    }
+
+    /// Moves to the next partition in lexicographic order of the restricted
+    /// growth strings. Returns `false` if the current one was the last.
+    fn advance(&mut self) -> bool {
+        let n = self.pool.len();
+
+        // Scan from the end, looking for an element that can move to a later block
+        while let Some(old) = self.blocks.pop() {
+            let used = self.blocks.iter().max().map_or(0, |&b| b + 1);
+            let left = n - self.blocks.len() - 1;
+            let limit = used.min(self.max_blocks.saturating_sub(1));
+            for block in old + 1..=limit {
+                let now_used = used.max(block + 1);
+                if now_used + left >= self.min_blocks {
+                    self.blocks.push(block);
+                    // Reset the elements after it to the smallest assignment
+                    return self.fill(now_used);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl<I> Iterator for SetPartitions<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    type Item = Vec<Vec<I::Item>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.start();
+        } else if !self.done && !self.advance() {
+            self.done = true;
+        }
+
+        if self.done {
+            return None;
+        }
+
+        self.remaining = self.remaining.map(|r| r - 1);
+        let mut partition: Vec<Vec<I::Item>> = Vec::new();
+        for (i, &block) in self.blocks.iter().enumerate() {
+            if block == partition.len() {
+                partition.push(Vec::new());
+            }
+            partition[block].push(self.pool[i].clone());
+        }
+        Some(partition)
+    }
+
+    fn count(mut self) -> usize {
+        if self.first {
+            self.start();
+        }
+
+        if self.done {
+            return 0;
+        }
+
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        if self.first {
+            let (low, high) = self.pool.it.size_hint();
+            let (min, max) = (self.min_blocks, self.max_blocks);
+            if min == 0 && max == 0 {
+                // Only the empty set has a partition into no blocks.
+                let low = if high == Some(0) { 1 } else { 0 };
+                let high = if low > 0 { Some(1) } else { Some(0) };
+                return (low, high);
+            }
+            let low = count_set_partitions(low, min, max).unwrap_or(usize::MAX);
+            let high = high.and_then(|n| count_set_partitions(n, min, max));
+            return (low, high);
+        }
+
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Counts the partitions of an `n`-element set into between `min` and `max`
+/// blocks, or `None` on overflow.
+///
+/// This is a Stirling number of the second kind when `min == max`, and the Bell
+/// number of `n` when the range is unbounded.
+fn count_set_partitions(n: usize, min: usize, max: usize) -> Option<usize> {
+    let max = max.min(n);
+    if min > max {
+        return Some(0);
+    }
+    if max <= 1 {
+        // Only the empty set has a partition into no blocks, and every other set
+        // has exactly one partition into one block.
+        return Some(if n == 0 { 1 } else { max });
+    }
+
+    // `S(n, k) >= k^(n - k)`, so counts for two or more blocks overflow unless
+    // the number of blocks is close to `n`.
+    if n - min.max(2) >= usize::BITS as usize {
+        return None;
+    }
+
+    // `row[t]` is `S(i, i - t)`, computed with `S(i, k) = k * S(i - 1, k) + S(i - 1, k - 1)`
+    // for `t <= n - min`, the only ones `S(n, min..=max)` depends on.
+    let width = n - min + 1;
+    let mut row: Vec<Option<usize>> = alloc::vec![Some(0); width];
+    row[0] = Some(1);
+    for i in 1..=n {
+        for t in (1..=i.min(width - 1)).rev() {
+            row[t] = row[t - 1].and_then(|s| s.checked_mul(i - t))
+                .and_then(|s| s.checked_add(row[t]?));
+        }
+    }
+
+    row[n - max..].iter().try_fold(0usize, |acc, &s| acc.checked_add(s?))
+}
+
+/// An iterator over the partitions of an integer.
+///
+/// See [`integer_partitions`] for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntegerPartitions {
+    // The current partition, in non-increasing order.
+    parts: Vec<usize>,
+    first: bool,
+    done: bool,
+    // Partitions left to yield, `None` on overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the partitions of `n`: the ways of writing `n` as a
+/// sum of positive integers, disregarding their order.
+///
+/// Iterator element type is `Vec<usize>`. Each partition lists its parts in
+/// non-increasing order, and partitions are produced in reverse lexicographic
+/// order, from `[n]` down to `[1, 1, ..., 1]`.
+///
+/// The number of partitions is computed up front, so `size_hint` is exact as
+/// long as it fits in `usize`.
+///
+/// ```
+/// use itertools::integer_partitions;
+///
+/// itertools::assert_equal(integer_partitions(4), vec![
+///     vec![4],
+///     vec![3, 1],
+///     vec![2, 2],
+///     vec![2, 1, 1],
+///     vec![1, 1, 1, 1],
+/// ]);
+/// assert_eq!(integer_partitions(30).count(), 5604);
+/// ```
+pub fn integer_partitions(n: usize) -> IntegerPartitions {
+    IntegerPartitions {
+        parts: if n == 0 { Vec::new() } else { alloc::vec![n] },
+        first: true,
+        done: false,
+        remaining: count_integer_partitions(n),
+    }
+}
+
+/// Computes the partition number of `n`, or `None` if it overflows `usize`.
+///
+/// This uses Euler's pentagonal number recurrence; partition numbers grow
+/// quickly, so the loop overflows (and stops) after a few hundred terms.
+fn count_integer_partitions(n: usize) -> Option<usize> {
+    let mut partitions: Vec<u128> = alloc::vec![1];
+    for m in 1..=n {
+        let mut total: i128 = 0;
+        for j in 1.. {
+            let pentagonals = [j * (3 * j - 1) / 2, j * (3 * j + 1) / 2];
+            if pentagonals[0] > m {
+                break;
+            }
+            for &g in pentagonals.iter().filter(|&&g| g <= m) {
+                let term = partitions[m - g] as i128;
+                total += if j % 2 == 1 { term } else { -term };
+            }
+        }
+        if total as u128 > usize::MAX as u128 {
+            return None;
+        }
+        partitions.push(total as u128);
+    }
+    Some(partitions[n] as usize)
+}
+
+impl IntegerPartitions {
+    /// Moves to the next partition. Returns `false` if the current one was the last.
+    fn advance(&mut self) -> bool {
+        // Take off the trailing ones and the smallest part larger than one
+        let mut rest = 0;
+        while self.parts.last() == Some(&1) {
+            self.parts.pop();
+            rest += 1;
+        }
+        let part = match self.parts.pop() {
+            Some(part) => part - 1,
+            None => return false,
+        };
+
+        // Redistribute them as parts no larger than the decremented one
+        rest += part + 1;
+        while rest > part {
+            self.parts.push(part);
+            rest -= part;
+        }
+        if rest > 0 {
+            self.parts.push(rest);
+        }
+        true
+    }
+}
+
+impl Iterator for IntegerPartitions {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.first {
+            self.first = false;
+        } else if !self.advance() {
+            self.done = true;
+            return None;
+        }
+
+        self.remaining = self.remaining.map(|r| r - 1);
+        Some(self.parts.clone())
+    }
+
+    fn count(self) -> usize {
+        if self.done {
+            return 0;
+        }
+
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(feature = "use_alloc")]
+use alloc::vec::Vec;
+
+/// An iterator over the swaps that step through all the permutations of `n`
+/// items with Heap's algorithm.
+///
+/// See [`permutation_swaps`] for more information.
+#[cfg(feature = "use_alloc")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct PermutationSwaps {
+    // `counters[i]` counts the swaps made at position `i` since the items before
+    // it were last reset, like the loop counters of the recursive algorithm.
+    counters: Vec<usize>,
+    i: usize,
+    // Swaps left to yield, `None` on overflow.
+    remaining: Option<usize>,
+}
+
+/// Return an iterator over the swaps that step through all the permutations of
+/// `n` items with Heap's algorithm.
+///
+/// Iterator element type is `(usize, usize)`: a pair of positions to swap.
+/// Starting from any arrangement of `n` items and applying the swaps in turn
+/// goes through every permutation of them exactly once, so there are `n! - 1`
+/// swaps. Unlike [`.permutations()`](crate::Itertools::permutations), nothing
+/// is allocated or cloned per permutation.
+///
+/// The number of swaps is computed up front, so `size_hint` is exact as long as
+/// it fits in `usize`.
+///
+/// ```
+/// use itertools::permutation_swaps;
+///
+/// let mut items = ['a', 'b', 'c'];
+/// let mut seen = vec![items];
+/// for (i, j) in permutation_swaps(items.len()) {
+///     items.swap(i, j);
+///     seen.push(items);
+/// }
+/// assert_eq!(seen, vec![
+///     ['a', 'b', 'c'],
+///     ['b', 'a', 'c'],
+///     ['c', 'a', 'b'],
+///     ['a', 'c', 'b'],
+///     ['b', 'c', 'a'],
+///     ['c', 'b', 'a'],
+/// ]);
+/// ```
+#[cfg(feature = "use_alloc")]
+pub fn permutation_swaps(n: usize) -> PermutationSwaps {
+    let permutations = (1..=n).try_fold(1usize, |acc, i| acc.checked_mul(i));
+    PermutationSwaps {
+        counters: alloc::vec![0; n],
+        i: 1,
+        remaining: permutations.map(|p| p - 1),
+    }
+}
+
+#[cfg(feature = "use_alloc")]
+impl Iterator for PermutationSwaps {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Scan up from the bottom, looking for a position with swaps left
+        while self.i < self.counters.len() {
+            let i = self.i;
+            if self.counters[i] < i {
+                let j = if i % 2 == 1 { self.counters[i] } else { 0 };
+                self.counters[i] += 1;
+                self.i = 1;
+                self.remaining = self.remaining.map(|r| r - 1);
+                return Some((j, i));
+            }
+
+            // Reset it, like leaving a level of the recursion
+            self.counters[i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+
+    fn count(self) -> usize {
+        match self.remaining {
+            Some(count) => count,
+            None => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(count) => (count, Some(count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Call `f` with every permutation of `items`, rearranging them in place.
+///
+/// The permutations are visited in the order of Heap's algorithm, starting with
+/// `items` as given: each one after the first differs from the previous one by
+/// a single swap, the one [`permutation_swaps`] yields. Nothing is allocated or
+/// cloned, and `items` is left in the last permutation visited.
+///
+/// ```
+/// let mut items = [1, 2, 3];
+/// let mut seen = Vec::new();
+/// itertools::for_each_permutation(&mut items, |p| seen.push(p[0] * 100 + p[1] * 10 + p[2]));
+/// assert_eq!(seen, vec![123, 213, 312, 132, 231, 321]);
+/// assert_eq!(items, [3, 2, 1]);
+/// ```
+pub fn for_each_permutation<T, F>(items: &mut [T], mut f: F)
+    where F: FnMut(&[T])
+{
+    f(items);
+    heap_swaps(items.len(), items, &mut f);
+}
+
+/// Visits every permutation of the first `k` items with Heap's algorithm, but
+/// the one they are in already, calling `f` after each swap.
+fn heap_swaps<T, F>(k: usize, items: &mut [T], f: &mut F)
+    where F: FnMut(&[T])
+{
+    if k <= 1 {
+        return;
+    }
+
+    for i in 0..k - 1 {
+        heap_swaps(k - 1, items, f);
+        let j = if k % 2 == 1 { 0 } else { i };
+        items.swap(j, k - 1);
+        f(items);
+    }
+    heap_swaps(k - 1, items, f);
+}
@@ -3,6 +3,7 @@ use std::fmt;
 use std::iter::once;
 
 use super::lazy_buffer::LazyBuffer;
+use super::size_hint;
 
 /// An iterator adaptor that iterates through all the `k`-permutations of the
 /// elements from an iterator.
@@ -13,13 +14,15 @@ use super::lazy_buffer::LazyBuffer;
 pub struct Permutations<I: Iterator> {
     vals: LazyBuffer<I>,
     state: PermutationState,
+    // Number of permutations left to yield, if split off by `split_at`.
+    limit: Option<usize>,
 }
 
 impl<I> Clone for Permutations<I>
     where I: Clone + Iterator,
           I::Item: Clone,
 {
-    clone_fields!(vals, state);
+    clone_fields!(vals, state, limit);
 }
 
 #[derive(Clone, Debug)]
@@ -56,7 +59,7 @@ impl<I> fmt::Debug for Permutations<I>
     where I: Iterator + fmt::Debug,
           I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Permutations, vals, state);
+    debug_fmt_fields!(Permutations, vals, state, limit);
 }
 
 pub fn permutations<I: Iterator>(iter: I, k: usize) -> Permutations<I> {
@@ -68,7 +71,8 @@ pub fn permutations<I: Iterator>(iter: I, k: usize) -> Permutations<I> {
 
         return Permutations {
             vals,
-            state
+            state,
+            limit: None,
         };
     }
 
@@ -89,7 +93,8 @@ pub fn permutations<I: Iterator>(iter: I, k: usize) -> Permutations<I> {
 
     Permutations {
         vals,
-        state
+        state,
+        limit: None,
     }
 }
 
@@ -101,9 +106,16 @@ where
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return None;
+            }
+            self.limit = Some(limit - 1);
+        }
+
         self.advance();
 
-        let &mut Permutations { ref vals, ref state } = self;
+        let &mut Permutations { ref vals, ref state, limit: _ } = self;
 
         match *state {
             PermutationState::StartUnknownLen { .. } => panic!("unexpected iterator state"),
@@ -124,7 +136,12 @@ where
     }
 
     fn count(self) -> usize {
-        let Permutations { vals, state } = self;
+        if self.limit.is_some() {
+            // Split off iterators are complete, so their size hint is exact.
+            return self.size_hint().0;
+        }
+
+        let Permutations { vals, state, limit: _ } = self;
 
         fn from_complete(complete_state: CompleteState) -> usize {
             match complete_state.remaining() {
@@ -155,7 +172,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        match self.state {
+        let hint = match self.state {
             PermutationState::StartUnknownLen { .. } |
             PermutationState::OngoingUnknownLen { .. } => (0, None), // TODO can we improve this lower bound?
             PermutationState::Complete(ref state) => match state.remaining() {
@@ -163,6 +180,11 @@ where
                 CompleteStateRemaining::Overflow => (::std::usize::MAX, None)
             }
             PermutationState::Empty => (0, Some(0))
+        };
+
+        match self.limit {
+            Some(limit) => size_hint::min(hint, (limit, Some(limit))),
+            None => hint,
         }
     }
 }
@@ -173,7 +195,7 @@ where
     I::Item: Clone
 {
     fn advance(&mut self) {
-        let &mut Permutations { ref mut vals, ref mut state } = self;
+        let &mut Permutations { ref mut vals, ref mut state, limit: _ } = self;
 
         *state = match *state {
             PermutationState::StartUnknownLen { k } => {
@@ -205,6 +227,91 @@ where
     }
 }
 
+impl<I> Permutations<I>
+where
+    I: Iterator + Clone,
+    I::Item: Clone
+{
+    /// Reads the rest of the source iterator, switching to the state for a
+    /// known number of elements.
+    fn complete(&mut self) {
+        self.vals.prefill(usize::MAX);
+        let n = self.vals.len();
+
+        self.state = match self.state {
+            PermutationState::StartUnknownLen { k } => {
+                PermutationState::Complete(CompleteState::Start { n, k })
+            }
+            PermutationState::OngoingUnknownLen { k, min_n } => {
+                let mut complete_state = CompleteState::Start { n, k };
+                complete_state.skip_ahead(min_n - k + 1);
+                PermutationState::Complete(complete_state)
+            }
+            _ => return,
+        };
+    }
+
+    /// Splits the remaining permutations into two iterators: one over the first
+    /// `index` of them, and one over the rest.
+    ///
+    /// The source iterator is consumed completely, and both halves start from
+    /// their position by rank rather than by stepping through the permutations
+    /// before it.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (front, back) = (0..3).permutations(3).split_at(2);
+    /// itertools::assert_equal(front, vec![vec![0, 1, 2], vec![0, 2, 1]]);
+    /// itertools::assert_equal(back, vec![
+    ///     vec![1, 0, 2],
+    ///     vec![1, 2, 0],
+    ///     vec![2, 0, 1],
+    ///     vec![2, 1, 0],
+    /// ]);
+    /// ```
+    pub fn split_at(mut self, index: usize) -> (Self, Self) {
+        self.complete();
+
+        let mut back = self.clone();
+        let left = self.limit.map_or(index, |limit| limit.min(index));
+        back.limit = back.limit.map(|limit| limit - left);
+        let moved = match back.state {
+            PermutationState::Complete(ref mut state) => state.skip_ahead(index),
+            _ => index == 0,
+        };
+        if !moved {
+            back.state = PermutationState::Empty;
+        }
+
+        self.limit = Some(left);
+        (self, back)
+    }
+
+    /// Splits the remaining permutations into `n` iterators over consecutive
+    /// ranges of them, whose lengths differ by at most one.
+    ///
+    /// This is convenient to divide the work between `n` threads. The source
+    /// iterator is consumed completely.
+    ///
+    /// **Panics** if `n` is zero, or if the number of permutations left is
+    /// greater than `usize::MAX`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = (0..4).permutations(2).split_into(5);
+    /// let lens: Vec<usize> = parts.iter().map(|part| part.size_hint().0).collect();
+    /// assert_eq!(lens, vec![3, 3, 2, 2, 2]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), (0..4).permutations(2));
+    /// ```
+    pub fn split_into(mut self, n: usize) -> Vec<Self> {
+        self.complete();
+        let len = self.size_hint().1.expect("Iterator count greater than usize::MAX");
+        crate::split::split_into(self, len, n, Self::split_at)
+    }
+}
+
 impl CompleteState {
     fn advance(&mut self) {
         *self = match *self {
@@ -241,6 +348,54 @@ impl CompleteState {
         }
     }
 
+    /// Moves past the next `steps` permutations. Returns `false` if there are
+    /// not that many left.
+    ///
+    /// `cycles` holds the number of permutations left after the current one in
+    /// a mixed radix, so this subtracts `steps` from it digit by digit. At each
+    /// position `i`, the current permutation then picks the index of rank
+    /// `n - 1 - i - cycles[i]` among those not picked yet, and the indices left
+    /// over follow in increasing order.
+    fn skip_ahead(&mut self, steps: usize) -> bool {
+        if steps == 0 {
+            return true;
+        }
+
+        if let CompleteState::Start { .. } = *self {
+            // Moving onto the first permutation takes one step.
+            self.advance();
+            return self.skip_ahead(steps - 1);
+        }
+
+        if let CompleteState::Ongoing { ref mut indices, ref mut cycles } = *self {
+            let n = indices.len();
+            let k = cycles.len();
+
+            let mut borrow = steps;
+            for (i, c) in cycles.iter_mut().enumerate().rev() {
+                let radix = n - i;
+                let (carry, digit) = (borrow / radix, borrow % radix);
+                if *c >= digit {
+                    *c -= digit;
+                    borrow = carry;
+                } else {
+                    *c += radix - digit;
+                    borrow = carry + 1;
+                }
+            }
+            if borrow > 0 {
+                return false;
+            }
+
+            let mut rest: Vec<usize> = (0..n).collect();
+            for (i, &c) in cycles.iter().enumerate() {
+                indices[i] = rest.remove(n - 1 - i - c);
+            }
+            indices[k..].copy_from_slice(&rest);
+        }
+        true
+    }
+
     fn remaining(&self) -> CompleteStateRemaining {
         use self::CompleteStateRemaining::{Known, Overflow};
 
@@ -1,8 +1,10 @@
 use std::fmt;
+use std::ops::{Bound, RangeBounds};
 use std::usize;
 use alloc::vec::Vec;
 
-use super::combinations::{Combinations, combinations};
+use super::combinations::{Combinations, checked_binomial, combinations};
+use super::lazy_buffer::LazyBuffer;
 use super::size_hint;
 
 /// An iterator to iterate through the powerset of the elements from an iterator.
@@ -12,22 +14,25 @@ use super::size_hint;
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Powerset<I: Iterator> {
     combs: Combinations<I>,
-    // Iterator `position` (equal to count of yielded elements).
-    pos: usize,
+    // Smallest and largest (inclusive) size of the subsets to yield.
+    min: usize,
+    max: usize,
+    // Number of subsets left to yield, if split off by `split_at`.
+    limit: Option<usize>,
 }
 
 impl<I> Clone for Powerset<I>
     where I: Clone + Iterator,
           I::Item: Clone,
 {
-    clone_fields!(combs, pos);
+    clone_fields!(combs, min, max, limit);
 }
 
 impl<I> fmt::Debug for Powerset<I>
     where I: Iterator + fmt::Debug,
           I::Item: fmt::Debug,
 {
-    debug_fmt_fields!(Powerset, combs, pos);
+    debug_fmt_fields!(Powerset, combs, min, max, limit);
 }
 
 /// Create a new `Powerset` from a clonable iterator.
@@ -35,9 +40,113 @@ pub fn powerset<I>(src: I) -> Powerset<I>
     where I: Iterator,
           I::Item: Clone,
 {
+    powerset_range(src, ..)
+}
+
+/// Create a new `Powerset` from a clonable iterator, limited to the subsets
+/// whose size lies in `sizes`.
+pub fn powerset_range<I, R>(src: I, sizes: R) -> Powerset<I>
+    where I: Iterator,
+          I::Item: Clone,
+          R: RangeBounds<usize>,
+{
+    let min = match sizes.start_bound() {
+        Bound::Included(&min) => min,
+        Bound::Excluded(&min) => min.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let max = match sizes.end_bound() {
+        Bound::Included(&max) => Some(max),
+        Bound::Excluded(&max) => max.checked_sub(1),
+        Bound::Unbounded => Some(usize::MAX),
+    };
+
+    let (min, max) = match max {
+        Some(max) if min <= max => (min, max),
+        // An empty range of sizes, which yields nothing
+        _ => (1, 0),
+    };
+
     Powerset {
-        combs: combinations(src, 0),
-        pos: 0,
+        combs: combinations(src, min),
+        min,
+        max,
+        limit: None,
+    }
+}
+
+impl<I> Powerset<I>
+    where I: Iterator + Clone,
+          I::Item: Clone,
+{
+    /// Moves past the next `steps` subsets without yielding them, jumping over
+    /// whole sizes at a time. Returns `false` if that passes the last subset.
+    fn skip_ahead(&mut self, mut steps: usize) -> bool {
+        let n = self.combs.n();
+        loop {
+            match self.combs.remaining_for(n) {
+                Some(left) if steps >= left => {
+                    let k = self.combs.k();
+                    if k >= self.max || k >= n {
+                        return false;
+                    }
+                    steps -= left;
+                    self.combs.reset(k + 1);
+                }
+                _ => return self.combs.skip_ahead(steps),
+            }
+        }
+    }
+
+    /// Splits the remaining subsets into two iterators: one over the first
+    /// `index` of them, and one over the rest.
+    ///
+    /// The source iterator is consumed completely, and both halves start from
+    /// their position by rank rather than by stepping through the subsets before
+    /// it.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let (front, back) = (0..3).powerset().split_at(3);
+    /// itertools::assert_equal(front, vec![vec![], vec![0], vec![1]]);
+    /// itertools::assert_equal(back, vec![vec![2], vec![0, 1], vec![0, 2], vec![1, 2], vec![0, 1, 2]]);
+    /// ```
+    pub fn split_at(mut self, index: usize) -> (Self, Self) {
+        self.combs.fill_pool();
+
+        let mut back = self.clone();
+        let left = self.limit.map_or(index, |limit| limit.min(index));
+        back.limit = back.limit.map(|limit| limit - left);
+        if self.min > self.max || !back.skip_ahead(index) {
+            back.limit = Some(0);
+        }
+
+        self.limit = Some(left);
+        (self, back)
+    }
+
+    /// Splits the remaining subsets into `n` iterators over consecutive ranges
+    /// of them, whose lengths differ by at most one.
+    ///
+    /// This is convenient to divide the work between `n` threads. The source
+    /// iterator is consumed completely.
+    ///
+    /// **Panics** if `n` is zero, or if the number of subsets left is greater
+    /// than `usize::MAX`.
+    ///
+    /// ```
+    /// use itertools::Itertools;
+    ///
+    /// let parts = (0..4).powerset().split_into(3);
+    /// let lens: Vec<usize> = parts.iter().map(|part| part.len()).collect();
+    /// assert_eq!(lens, vec![6, 5, 5]);
+    /// itertools::assert_equal(parts.into_iter().flatten(), (0..4).powerset());
+    /// ```
+    pub fn split_into(mut self, n: usize) -> Vec<Self> {
+        self.combs.fill_pool();
+        let len = self.size_hint().1.expect("Iterator count greater than usize::MAX");
+        crate::split::split_into(self, len, n, Self::split_at)
     }
 }
 
@@ -49,35 +158,193 @@ impl<I> Iterator for Powerset<I>
     type Item = Vec<I::Item>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(elt) = self.combs.next() {
-            self.pos = self.pos.saturating_add(1);
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return None;
+            }
+            self.limit = Some(limit - 1);
+        }
+
+        if self.min > self.max {
+            None
+        } else if let Some(elt) = self.combs.next() {
             Some(elt)
-        } else if self.combs.k() < self.combs.n()
-            || self.combs.k() == 0
+        } else if self.combs.k() < self.max
+            && (self.combs.k() < self.combs.n() || self.combs.k() == 0)
         {
             self.combs.reset(self.combs.k() + 1);
-            self.combs.next().map(|elt| {
-                self.pos = self.pos.saturating_add(1);
-                elt
-            })
+            self.combs.next()
         } else {
             None
         }
     }
 
+    fn count(self) -> usize {
+        if self.min > self.max {
+            return 0;
+        }
+
+        let (k, max, limit) = (self.combs.k(), self.max, self.limit);
+        let (n, remaining) = self.combs.n_and_remaining();
+        match (remaining.and_then(|r| r.checked_add(count_larger_subsets(n, k, max)?)), limit) {
+            (Some(count), Some(limit)) => count.min(limit),
+            (Some(count), None) | (None, Some(count)) => count,
+            (None, None) => panic!("Iterator count greater than usize::MAX"),
+        }
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.min > self.max {
+            return (0, Some(0));
+        }
+
         // Total bounds for source iterator.
-        let src_total = size_hint::add_scalar(self.combs.src().size_hint(), self.combs.n());
+        let (low, high) = size_hint::add_scalar(self.combs.src().size_hint(), self.combs.n());
 
-        // Total bounds for self ( length(powerset(set) == 2 ^ length(set) )
-        let self_total = size_hint::pow_scalar_base(2, src_total);
+        // What is left of the current size, and all subsets of the larger sizes.
+        let remaining = |n| {
+            let larger = count_larger_subsets(n, self.combs.k(), self.max)?;
+            self.combs.remaining_for(n)?.checked_add(larger)
+        };
+        let hint = (remaining(low).unwrap_or(usize::MAX), high.and_then(remaining));
+        match self.limit {
+            Some(limit) => size_hint::min(hint, (limit, Some(limit))),
+            None => hint,
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for Powerset<I>
+    where
+        I: ExactSizeIterator,
+        I::Item: Clone,
+{}
+
+/// Counts the subsets of an `n`-element set with between `min` and `max`
+/// elements, or `None` on overflow.
+fn count_subsets(n: usize, min: usize, max: usize) -> Option<usize> {
+    (min..=max.min(n)).try_fold(0usize, |acc, k| acc.checked_add(checked_binomial(n, k)?))
+}
 
-        if self.pos < usize::MAX {
-            // Subtract count of elements already yielded from total.
-            size_hint::sub_scalar(self_total, self.pos)
+/// Counts the subsets of an `n`-element set with more than `k` but at most `max`
+/// elements, or `None` on overflow.
+fn count_larger_subsets(n: usize, k: usize, max: usize) -> Option<usize> {
+    match k.checked_add(1) {
+        Some(min) => count_subsets(n, min, max),
+        None => Some(0),
+    }
+}
+
+/// An iterator to iterate through the powerset of the elements from an iterator
+/// in Gray-code order, so that consecutive subsets differ by a single element.
+///
+/// See [`.powerset_gray()`](crate::Itertools::powerset_gray) for more
+/// information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct PowersetGray<I: Iterator> {
+    pool: LazyBuffer<I>,
+    // Whether each pool element is in the current subset.
+    members: Vec<bool>,
+    // Number of steps taken from the empty set.
+    step: usize,
+    done: bool,
+}
+
+impl<I> Clone for PowersetGray<I>
+    where I: Clone + Iterator,
+          I::Item: Clone,
+{
+    clone_fields!(pool, members, step, done);
+}
+
+impl<I> fmt::Debug for PowersetGray<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(PowersetGray, pool, members, step, done);
+}
+
+/// A change between two consecutive subsets yielded by [`PowersetGray`].
+///
+/// See [`.powerset_gray()`](crate::Itertools::powerset_gray) for more information.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SubsetChange<T> {
+    /// The element was added to the subset.
+    Added(T),
+    /// The element was removed from the subset.
+    Removed(T),
+}
+
+/// Create a new `PowersetGray` from a clonable iterator.
+pub fn powerset_gray<I>(src: I) -> PowersetGray<I>
+    where I: Iterator,
+          I::Item: Clone,
+{
+    PowersetGray {
+        pool: LazyBuffer::new(src),
+        members: Vec::new(),
+        step: 0,
+        done: false,
+    }
+}
+
+impl<I> Iterator for PowersetGray<I>
+    where
+        I: Iterator,
+        I::Item: Clone,
+{
+    type Item = (SubsetChange<I::Item>, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // Step `i` of the binary reflected Gray code flips bit `i.trailing_zeros()`.
+        // The code for `n` elements is a prefix of the code for `n + 1`, so the
+        // pool only grows when a new element is added for the first time.
+        let step = match self.step.checked_add(1) {
+            Some(step) => step,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let bit = step.trailing_zeros() as usize;
+        if bit == self.pool.len() && !self.pool.get_next() {
+            self.done = true;
+            return None;
+        }
+        if bit == self.members.len() {
+            self.members.push(false);
+        }
+
+        self.step = step;
+        self.members[bit] = !self.members[bit];
+        let elt = self.pool[bit].clone();
+        let change = if self.members[bit] {
+            SubsetChange::Added(elt)
         } else {
-            // Fallback: self.pos is saturated and no longer reliable.
-            (0, self_total.1)
+            SubsetChange::Removed(elt)
+        };
+        let subset = self.members.iter().enumerate()
+            .filter(|&(_, &member)| member)
+            .map(|(i, _)| self.pool[i].clone())
+            .collect();
+
+        Some((change, subset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
         }
+
+        // Total bounds for source iterator.
+        let src_total = size_hint::add_scalar(self.pool.it.size_hint(), self.pool.len());
+
+        // Every subset but the empty one is reached by a step.
+        let self_total = size_hint::sub_scalar(size_hint::pow_scalar_base(2, src_total), 1);
+        size_hint::sub_scalar(self_total, self.step)
     }
 }
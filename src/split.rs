@@ -0,0 +1,24 @@
+use alloc::vec::Vec;
+
+/// Splits `it`, which has `len` items left, into `parts` iterators over
+/// consecutive ranges of as equal lengths as possible, the longer ones first.
+///
+/// `split_at` must split off the given number of items at the front.
+///
+/// **Panics** if `parts` is zero.
+pub(crate) fn split_into<T, F>(it: T, len: usize, parts: usize, mut split_at: F) -> Vec<T>
+    where F: FnMut(T, usize) -> (T, T),
+{
+    assert!(parts != 0, "cannot split into zero parts");
+
+    let mut split = Vec::with_capacity(parts);
+    let mut rest = it;
+    for i in 1..parts {
+        let part_len = len / parts + if i <= len % parts { 1 } else { 0 };
+        let (front, back) = split_at(rest, part_len);
+        split.push(front);
+        rest = back;
+    }
+    split.push(rest);
+    split
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::fmt;
+
+// Marks the ends of the list of recent keys
+const NONE: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+struct RecentNode<K> {
+    key: K,
+    older: usize,
+    newer: usize,
+}
+
+/// The last `capacity` distinct keys, as a list from the least to the most
+/// recently used, stored in a `Vec` whose slots are reused, and indexed by a
+/// `HashMap`.
+#[derive(Clone, Debug)]
+struct RecentKeys<K> {
+    slots: HashMap<K, usize>,
+    nodes: Vec<RecentNode<K>>,
+    oldest: usize,
+    newest: usize,
+    capacity: usize,
+}
+
+impl<K> RecentKeys<K>
+    where K: Clone + Eq + Hash,
+{
+    fn new(capacity: usize) -> Self {
+        RecentKeys {
+            slots: HashMap::new(),
+            nodes: Vec::new(),
+            oldest: NONE,
+            newest: NONE,
+            capacity,
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let RecentNode { older, newer, .. } = self.nodes[slot];
+        match older {
+            NONE => self.oldest = newer,
+            older => self.nodes[older].newer = newer,
+        }
+        match newer {
+            NONE => self.newest = older,
+            newer => self.nodes[newer].older = older,
+        }
+    }
+
+    fn link_newest(&mut self, slot: usize) {
+        self.nodes[slot].older = self.newest;
+        self.nodes[slot].newer = NONE;
+        match self.newest {
+            NONE => self.oldest = slot,
+            newest => self.nodes[newest].newer = slot,
+        }
+        self.newest = slot;
+    }
+
+    /// Makes `key` the most recently used, and returns `true` if it was not
+    /// among the recent keys.
+    fn use_key(&mut self, key: &K) -> bool {
+        if let Some(&slot) = self.slots.get(key) {
+            self.unlink(slot);
+            self.link_newest(slot);
+            return false;
+        }
+        if self.capacity == 0 {
+            return true;
+        }
+        let slot = if self.nodes.len() < self.capacity {
+            self.nodes.push(RecentNode { key: key.clone(), older: NONE, newer: NONE });
+            self.nodes.len() - 1
+        } else {
+            // Forget the least recently used key and reuse its slot
+            let slot = self.oldest;
+            self.unlink(slot);
+            self.slots.remove(&self.nodes[slot].key);
+            self.nodes[slot].key = key.clone();
+            slot
+        };
+        self.link_newest(slot);
+        self.slots.insert(key.clone(), slot);
+        true
+    }
+}
+
+/// An iterator adapter to filter out the elements equal to one of the last
+/// few distinct elements.
+///
+/// See [`.unique_recent()`](crate::Itertools::unique_recent) for more
+/// information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct UniqueRecent<I: Iterator> {
+    iter: I,
+    recent: RecentKeys<I::Item>,
+}
+
+impl<I> fmt::Debug for UniqueRecent<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: fmt::Debug,
+{
+    debug_fmt_fields!(UniqueRecent, iter, recent);
+}
+
+/// Create a new `UniqueRecent` iterator.
+pub fn unique_recent<I>(iter: I, n: usize) -> UniqueRecent<I>
+    where I: Iterator,
+          I::Item: Clone + Eq + Hash,
+{
+    UniqueRecent {
+        iter,
+        recent: RecentKeys::new(n),
+    }
+}
+
+impl<I> Iterator for UniqueRecent<I>
+    where I: Iterator,
+          I::Item: Clone + Eq + Hash,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let recent = &mut self.recent;
+        self.iter.by_ref().find(|v| recent.use_key(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        ((low > 0 && self.recent.slots.is_empty()) as usize, hi)
+    }
+}
+
+impl<I> DoubleEndedIterator for UniqueRecent<I>
+    where I: DoubleEndedIterator,
+          I::Item: Clone + Eq + Hash,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let recent = &mut self.recent;
+        self.iter.by_ref().rfind(|v| recent.use_key(v))
+    }
+}
+
+/// A Bloom filter: a set of `u64` words seen as bits, of which each element
+/// sets the `hashes` bits it hashes to.
+#[derive(Clone, Debug)]
+struct BloomFilter<S> {
+    words: Vec<u64>,
+    bits: u64,
+    hashes: u32,
+    inserted: usize,
+    hasher: S,
+}
+
+impl<S: BuildHasher> BloomFilter<S> {
+    fn new(bits: usize, hashes: u32, hasher: S) -> Self {
+        assert!(bits != 0, "a Bloom filter needs at least one bit");
+        assert!(hashes != 0, "a Bloom filter needs at least one hash");
+        BloomFilter {
+            words: vec![0; (bits - 1) / 64 + 1],
+            bits: bits as u64,
+            hashes,
+            inserted: 0,
+            hasher,
+        }
+    }
+
+    /// Sets the bits of `elt`, and returns `true` if they were not all set.
+    fn insert<T: Hash + ?Sized>(&mut self, elt: &T) -> bool {
+        let mut state = self.hasher.build_hasher();
+        elt.hash(&mut state);
+        // The bits are those of the hashes `h1 + i * h2`, which are as good
+        // as independent ones (Kirsch and Mitzenmacher); `h2` is odd so that
+        // they differ.
+        let h1 = state.finish();
+        state.write_u8(0xff);
+        let h2 = state.finish() | 1;
+        let mut new = false;
+        for i in 0..self.hashes as u64 {
+            let bit = h1.wrapping_add(i.wrapping_mul(h2)) % self.bits;
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            new |= self.words[word] & mask == 0;
+            self.words[word] |= mask;
+        }
+        if new {
+            self.inserted += 1;
+        }
+        new
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        let (k, m, n) = (self.hashes as f64, self.bits as f64, self.inserted as f64);
+        (1. - (-k * n / m).exp()).powf(k)
+    }
+}
+
+/// An iterator adapter to filter out duplicate elements, and possibly some
+/// others, remembering them in a Bloom filter of a fixed size.
+///
+/// See [`.unique_approx()`](crate::Itertools::unique_approx) for more
+/// information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct UniqueApprox<I, S = RandomState> {
+    iter: I,
+    filter: BloomFilter<S>,
+}
+
+impl<I, S> fmt::Debug for UniqueApprox<I, S>
+    where I: fmt::Debug,
+          S: fmt::Debug,
+{
+    debug_fmt_fields!(UniqueApprox, iter, filter);
+}
+
+/// Create a new `UniqueApprox` iterator.
+pub fn unique_approx<I>(iter: I, bits: usize, hashes: u32) -> UniqueApprox<I>
+    where I: Iterator,
+          I::Item: Hash,
+{
+    unique_approx_with_hasher(iter, bits, hashes, RandomState::new())
+}
+
+/// Create a new `UniqueApprox` iterator that hashes with `hasher`.
+pub fn unique_approx_with_hasher<I, S>(iter: I, bits: usize, hashes: u32, hasher: S)
+    -> UniqueApprox<I, S>
+    where I: Iterator,
+          I::Item: Hash,
+          S: BuildHasher,
+{
+    UniqueApprox {
+        iter,
+        filter: BloomFilter::new(bits, hashes, hasher),
+    }
+}
+
+impl<I, S: BuildHasher> UniqueApprox<I, S> {
+    /// Returns the estimated probability that the next new element is taken
+    /// for a duplicate and filtered out, given the elements produced so far.
+    ///
+    /// With `m` bits, `k` hashes and `n` elements produced, this is
+    /// `(1 - exp(-k * n / m))^k`.
+    pub fn false_positive_rate(&self) -> f64 {
+        self.filter.false_positive_rate()
+    }
+}
+
+impl<I, S> Iterator for UniqueApprox<I, S>
+    where I: Iterator,
+          I::Item: Hash,
+          S: BuildHasher,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let filter = &mut self.filter;
+        self.iter.by_ref().find(|v| filter.insert(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        ((low > 0 && self.filter.inserted == 0) as usize, hi)
+    }
+}
+
+impl<I, S> DoubleEndedIterator for UniqueApprox<I, S>
+    where I: DoubleEndedIterator,
+          I::Item: Hash,
+          S: BuildHasher,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let filter = &mut self.filter;
+        self.iter.by_ref().rfind(|v| filter.insert(v))
+    }
+}
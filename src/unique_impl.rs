@@ -1,8 +1,9 @@
 
 use std::collections::HashMap;
-use std::collections::hash_map::{Entry};
+use std::collections::hash_map::{Entry, IntoIter};
 use std::hash::Hash;
 use std::fmt;
+use std::iter::FusedIterator;
 
 /// An iterator adapter to filter out duplicate elements.
 ///
@@ -36,15 +37,74 @@ pub fn unique_by<I, V, F>(iter: I, f: F) -> UniqueBy<I, V, F>
     }
 }
 
-// count the number of new unique keys in iterable (`used` is the set already seen)
-fn count_new_keys<I, K>(mut used: HashMap<K, ()>, iterable: I) -> usize
+// What is remembered of each key seen, which decides when its elements are
+// produced: `()` for the first element only, and for `duplicates` whether the
+// key was produced yet, to produce its second element only.
+trait KeyState: Sized {
+    // The state of a key seen for the first time, and whether to produce it
+    fn first() -> (Self, bool);
+    // Updates the state of a key seen again, and returns whether to produce it
+    fn again(&mut self) -> bool;
+}
+
+impl KeyState for () {
+    fn first() -> (Self, bool) {
+        ((), true)
+    }
+
+    fn again(&mut self) -> bool {
+        false
+    }
+}
+
+impl KeyState for bool {
+    fn first() -> (Self, bool) {
+        (false, false)
+    }
+
+    fn again(&mut self) -> bool {
+        !std::mem::replace(self, true)
+    }
+}
+
+// Marks `key` as seen once more, and returns whether to produce its element
+fn see_key<K, S>(used: &mut HashMap<K, S>, key: K) -> bool
+    where K: Hash + Eq,
+          S: KeyState,
+{
+    match used.entry(key) {
+        Entry::Vacant(entry) => {
+            let (state, produce) = S::first();
+            entry.insert(state);
+            produce
+        }
+        Entry::Occupied(mut entry) => entry.get_mut().again(),
+    }
+}
+
+// Like `see_key`, but only clones `key` the first time it is seen
+fn see_key_ref<K, S>(used: &mut HashMap<K, S>, key: &K) -> bool
+    where K: Hash + Eq + Clone,
+          S: KeyState,
+{
+    match used.get_mut(key) {
+        None => {
+            let (state, produce) = S::first();
+            used.insert(key.clone(), state);
+            produce
+        }
+        Some(state) => state.again(),
+    }
+}
+
+// count the number of keys in iterable whose elements are produced (`used`
+// holds the keys already seen)
+fn count_new_keys<I, K, S>(mut used: HashMap<K, S>, iterable: I) -> usize
     where I: IntoIterator<Item=K>,
           K: Hash + Eq,
+          S: KeyState,
 {
-    let iter = iterable.into_iter();
-    let current_used = used.len();
-    used.extend(iter.map(|key| (key, ())));
-    used.len() - current_used
+    iterable.into_iter().fold(0, |count, key| count + see_key(&mut used, key) as usize)
 }
 
 impl<I, V, F> Iterator for UniqueBy<I, V, F>
@@ -164,3 +224,178 @@ pub fn unique<I>(iter: I) -> Unique<I>
         }
     }
 }
+
+/// An iterator adapter to produce the elements that are duplicates of earlier
+/// ones, once each.
+///
+/// See [`.duplicates_by()`](crate::Itertools::duplicates_by) for more
+/// information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct DuplicatesBy<I: Iterator, V, F> {
+    iter: I,
+    // Whether each key seen has been produced as a duplicate yet
+    used: HashMap<V, bool>,
+    f: F,
+}
+
+impl<I, V, F> fmt::Debug for DuplicatesBy<I, V, F>
+    where I: Iterator + fmt::Debug,
+          V: fmt::Debug + Hash + Eq,
+{
+    debug_fmt_fields!(DuplicatesBy, iter, used);
+}
+
+/// Create a new `DuplicatesBy` iterator.
+pub fn duplicates_by<I, V, F>(iter: I, f: F) -> DuplicatesBy<I, V, F>
+    where V: Eq + Hash,
+          F: FnMut(&I::Item) -> V,
+          I: Iterator,
+{
+    DuplicatesBy {
+        iter,
+        used: HashMap::new(),
+        f,
+    }
+}
+
+impl<I, V, F> Iterator for DuplicatesBy<I, V, F>
+    where I: Iterator,
+          V: Eq + Hash,
+          F: FnMut(&I::Item) -> V
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for v in self.iter.by_ref() {
+            let key = (self.f)(&v);
+            if see_key(&mut self.used, key) {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    fn count(self) -> usize {
+        let mut key_f = self.f;
+        count_new_keys(self.used, self.iter.map(move |elt| key_f(&elt)))
+    }
+}
+
+impl<I, V, F> DoubleEndedIterator for DuplicatesBy<I, V, F>
+    where I: DoubleEndedIterator,
+          V: Eq + Hash,
+          F: FnMut(&I::Item) -> V
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(v) = self.iter.next_back() {
+            let key = (self.f)(&v);
+            if see_key(&mut self.used, key) {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+
+impl<I> Iterator for Duplicates<I>
+    where I: Iterator,
+          I::Item: Eq + Hash + Clone
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let used = &mut self.iter.used;
+        self.iter.iter.by_ref().find(|v| see_key_ref(used, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.iter.size_hint().1)
+    }
+
+    fn count(self) -> usize {
+        count_new_keys(self.iter.used, self.iter.iter)
+    }
+}
+
+impl<I> DoubleEndedIterator for Duplicates<I>
+    where I: DoubleEndedIterator,
+          I::Item: Eq + Hash + Clone
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let used = &mut self.iter.used;
+        self.iter.iter.by_ref().rfind(|v| see_key_ref(used, v))
+    }
+}
+
+/// An iterator adapter to produce the elements that are duplicates of earlier
+/// ones, once each.
+///
+/// See [`.duplicates()`](crate::Itertools::duplicates) for more information.
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Duplicates<I: Iterator> {
+    iter: DuplicatesBy<I, I::Item, ()>,
+}
+
+impl<I> fmt::Debug for Duplicates<I>
+    where I: Iterator + fmt::Debug,
+          I::Item: Hash + Eq + fmt::Debug,
+{
+    debug_fmt_fields!(Duplicates, iter);
+}
+
+/// Create a new `Duplicates` iterator.
+pub fn duplicates<I>(iter: I) -> Duplicates<I>
+    where I: Iterator,
+          I::Item: Eq + Hash,
+{
+    Duplicates {
+        iter: DuplicatesBy {
+            iter,
+            used: HashMap::new(),
+            f: (),
+        }
+    }
+}
+
+/// An iterator over the elements that occur more than once in an iterator,
+/// with their number of occurrences.
+///
+/// See [`.duplicates_with_count()`](crate::Itertools::duplicates_with_count)
+/// for more information.
+#[derive(Debug)]
+pub struct DuplicatesWithCount<K> {
+    iter: IntoIter<K, usize>,
+}
+
+/// Create a new `DuplicatesWithCount` iterator.
+pub fn duplicates_with_count<I>(iter: I) -> DuplicatesWithCount<I::Item>
+    where I: Iterator,
+          I::Item: Eq + Hash,
+{
+    let mut counts = HashMap::new();
+    iter.for_each(|item| *counts.entry(item).or_insert(0) += 1);
+    DuplicatesWithCount { iter: counts.into_iter() }
+}
+
+impl<K> Iterator for DuplicatesWithCount<K> {
+    type Item = (K, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().find(|&(_, n)| n > 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<K> FusedIterator for DuplicatesWithCount<K> {}
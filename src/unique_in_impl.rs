@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::map_kind::MapKind;
+
+/// An iterator adapter to filter out duplicate elements, remembering them in a
+/// map of kind `M`.
+///
+/// See [`.unique_by_in()`](crate::Itertools::unique_by_in) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct UniqueByIn<I, V, F, M>
+    where M: MapKind<V, ()>,
+{
+    iter: I,
+    kind: M,
+    used: M::Map,
+    f: F,
+}
+
+impl<I, V, F, M> Clone for UniqueByIn<I, V, F, M>
+    where I: Clone,
+          F: Clone,
+          M: MapKind<V, ()> + Clone,
+          M::Map: Clone,
+{
+    clone_fields!(iter, kind, used, f);
+}
+
+impl<I, V, F, M> fmt::Debug for UniqueByIn<I, V, F, M>
+    where I: fmt::Debug,
+          M: MapKind<V, ()>,
+          M::Map: fmt::Debug,
+{
+    debug_fmt_fields!(UniqueByIn, iter, used);
+}
+
+/// Create a new `UniqueByIn` iterator.
+pub fn unique_by_in<I, V, F, M>(iter: I, kind: M, f: F) -> UniqueByIn<I, V, F, M>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> V,
+          M: MapKind<V, ()>,
+{
+    UniqueByIn {
+        iter,
+        used: kind.new_map(),
+        kind,
+        f,
+    }
+}
+
+// count the number of new unique keys in iterable (`used` is the set already seen)
+fn count_new_keys<I, K, M>(kind: M, mut used: M::Map, iterable: I) -> usize
+    where I: IntoIterator<Item=K>,
+          M: MapKind<K, ()>,
+{
+    let current_used = kind.len(&used);
+    iterable.into_iter().for_each(|key| {
+        kind.insert(&mut used, key, ());
+    });
+    kind.len(&used) - current_used
+}
+
+impl<I, V, F, M> Iterator for UniqueByIn<I, V, F, M>
+    where I: Iterator,
+          F: FnMut(&I::Item) -> V,
+          M: MapKind<V, ()>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for v in self.iter.by_ref() {
+            let key = (self.f)(&v);
+            if self.kind.insert(&mut self.used, key, ()).is_none() {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        ((low > 0 && self.kind.len(&self.used) == 0) as usize, hi)
+    }
+
+    fn count(self) -> usize {
+        let mut key_f = self.f;
+        count_new_keys(self.kind, self.used, self.iter.map(move |elt| key_f(&elt)))
+    }
+}
+
+impl<I, V, F, M> DoubleEndedIterator for UniqueByIn<I, V, F, M>
+    where I: DoubleEndedIterator,
+          F: FnMut(&I::Item) -> V,
+          M: MapKind<V, ()>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(v) = self.iter.next_back() {
+            let key = (self.f)(&v);
+            if self.kind.insert(&mut self.used, key, ()).is_none() {
+                return Some(v);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator adapter to filter out duplicate elements, remembering them in a
+/// map of kind `M`.
+///
+/// See [`.unique_in()`](crate::Itertools::unique_in) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct UniqueIn<I, M>
+    where I: Iterator,
+          M: MapKind<I::Item, ()>,
+{
+    iter: I,
+    kind: M,
+    used: M::Map,
+}
+
+impl<I, M> Clone for UniqueIn<I, M>
+    where I: Iterator + Clone,
+          M: MapKind<I::Item, ()> + Clone,
+          M::Map: Clone,
+{
+    clone_fields!(iter, kind, used);
+}
+
+impl<I, M> fmt::Debug for UniqueIn<I, M>
+    where I: Iterator + fmt::Debug,
+          M: MapKind<I::Item, ()>,
+          M::Map: fmt::Debug,
+{
+    debug_fmt_fields!(UniqueIn, iter, used);
+}
+
+/// Create a new `UniqueIn` iterator.
+pub fn unique_in<I, M>(iter: I, kind: M) -> UniqueIn<I, M>
+    where I: Iterator,
+          M: MapKind<I::Item, ()>,
+{
+    UniqueIn {
+        iter,
+        used: kind.new_map(),
+        kind,
+    }
+}
+
+impl<I, M> Iterator for UniqueIn<I, M>
+    where I: Iterator,
+          I::Item: Clone,
+          M: MapKind<I::Item, ()>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for v in self.iter.by_ref() {
+            if self.kind.get_mut(&mut self.used, &v).is_none() {
+                self.kind.insert(&mut self.used, v.clone(), ());
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, hi) = self.iter.size_hint();
+        ((low > 0 && self.kind.len(&self.used) == 0) as usize, hi)
+    }
+
+    fn count(self) -> usize {
+        count_new_keys(self.kind, self.used, self.iter)
+    }
+}
+
+impl<I, M> DoubleEndedIterator for UniqueIn<I, M>
+    where I: DoubleEndedIterator,
+          I::Item: Clone,
+          M: MapKind<I::Item, ()>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(v) = self.iter.next_back() {
+            if self.kind.get_mut(&mut self.used, &v).is_none() {
+                self.kind.insert(&mut self.used, v.clone(), ());
+                return Some(v);
+            }
+        }
+        None
+    }
+}
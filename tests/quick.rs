@@ -735,6 +735,45 @@ quickcheck! {
 
     }
 
+    fn combinations_nth(n: u8, k: u8, steps: Vec<u8>) -> () {
+        let (n, k) = (n % 10, k % 5);
+        let mut fast = (0..n).combinations(k as usize);
+        let mut slow = (0..n).combinations(k as usize);
+        for &s in &steps {
+            let s = (s % 8) as usize;
+            for _ in 0..s {
+                slow.next();
+            }
+            assert_eq!(fast.nth(s), slow.next());
+        }
+    }
+
+    fn multiset_permutations_distinct(a: Vec<u8>, k: usize) -> () {
+        let a: Vec<u8> = a.into_iter().take(6).map(|x| x % 3).collect();
+        let k = k % 7;
+        let expected = a.iter().permutations(k).unique().sorted().collect_vec();
+        let actual = a.iter().multiset_permutations(k).collect_vec();
+        assert_eq!(expected, actual);
+    }
+
+    fn multiset_permutations_size(a: Vec<u8>, k: usize) -> bool {
+        let a: Vec<u8> = a.into_iter().take(6).map(|x| x % 3).collect();
+        exact_size_for_this(a.iter().multiset_permutations(k % 7))
+    }
+
+    fn multiset_permutations_count(a: Vec<u8>, k: usize) -> bool {
+        let a: Vec<u8> = a.into_iter().take(6).map(|x| x % 3).collect();
+        correct_count(|| a.iter().multiset_permutations(k % 7))
+    }
+
+    fn array_combinations_equal(a: Vec<u8>) -> bool {
+        let a = &a[..a.len().min(10)];
+        itertools::equal(
+            a.iter().array_combinations::<3>().map(|c| c.to_vec()),
+            a.iter().combinations(3),
+        )
+    }
+
     fn permutations_count(n: usize, k: usize) -> bool {
         let n = n % 6;
 
@@ -745,6 +784,127 @@ quickcheck! {
         correct_size_hint(a.take(5).permutations(k))
     }
 
+    fn permutation_swaps_size(n: usize) -> bool {
+        correct_size_hint(itertools::permutation_swaps(n % 7))
+    }
+
+    fn derangements_filter_permutations(n: u8) -> bool {
+        let n = n as usize % 7;
+        let filtered = (0..n).permutations(n).filter(|p| p.iter().enumerate().all(|(i, &x)| i != x));
+        itertools::equal((0..n).derangements(), filtered)
+    }
+
+    fn derangements_size(a: Iter<u16>) -> bool {
+        correct_size_hint(a.take(6).derangements())
+    }
+
+    fn necklaces_are_smallest_rotations(m: u8, k: u8) -> bool {
+        let (m, k) = (m as usize % 4, k as usize % 6);
+        fn rotations(s: &[usize]) -> Vec<Vec<usize>> {
+            (0..s.len()).map(|r| s[r..].iter().chain(&s[..r]).copied().collect()).collect()
+        }
+        let reflections = |s: &[usize]| rotations(&s.iter().rev().copied().collect_vec());
+
+        let sequences = || (0..m).cartesian_power_vec(k);
+        let necklaces = sequences().filter(|s| rotations(s).iter().all(|r| s <= r));
+        let bracelets = sequences()
+            .filter(|s| rotations(s).iter().chain(&reflections(s)).all(|r| s <= r));
+        itertools::equal((0..m).necklaces(k), necklaces) &&
+            itertools::equal((0..m).bracelets(k), bracelets)
+    }
+
+    fn necklaces_size(a: Iter<u16>, k: u8) -> bool {
+        let k = k as usize % 5;
+        correct_size_hint(a.clone().take(5).necklaces(k)) &&
+            correct_size_hint(a.take(5).bracelets(k))
+    }
+
+    fn index_combinatorics_match_adaptors(n: u8, k: u8) -> bool {
+        let (n, k) = (n as usize % 7, k as usize % 5);
+        itertools::equal(itertools::index_combinations(n, k), (0..n).combinations(k)) &&
+            itertools::equal(itertools::index_permutations(n, k), (0..n).permutations(k)) &&
+            itertools::equal(itertools::index_combinations_with_replacement(n, k),
+                             (0..n).combinations_with_replacement(k))
+    }
+
+    fn index_combinatorics_both_ends(n: u8, k: u8, ends: Vec<bool>) -> bool {
+        let (n, k) = (n as usize % 7, k as usize % 5);
+        fn check<I>(iter: I, ends: &[bool]) -> bool
+            where I: DoubleEndedIterator + Clone,
+                  I::Item: PartialEq
+        {
+            let mut expected = iter.clone().collect::<Vec<_>>().into_iter();
+            let mut iter = iter;
+            for &front in ends {
+                if iter.size_hint() != expected.size_hint() {
+                    return false;
+                }
+                let (a, b) = if front {
+                    (iter.next(), expected.next())
+                } else {
+                    (iter.next_back(), expected.next_back())
+                };
+                if a != b {
+                    return false;
+                }
+            }
+            true
+        }
+        check(itertools::index_combinations(n, k), &ends) &&
+            check(itertools::index_permutations(n, k), &ends) &&
+            check(itertools::index_combinations_with_replacement(n, k), &ends) &&
+            check(itertools::index_array_permutations::<2>(n), &ends)
+    }
+
+    fn iter_bits_matches_filter(mask: u64) -> bool {
+        use itertools::IterBits;
+        itertools::equal(mask.iter_bits(), (0..64).filter(|&i| mask >> i & 1 == 1)) &&
+            itertools::equal(mask.iter_bits().rev(), (0..64).rev().filter(|&i| mask >> i & 1 == 1))
+    }
+
+    fn subsets_of_mask_match_powerset(mask: u16, ends: Vec<bool>) -> bool {
+        use itertools::IterBits;
+        let mask = mask as u64 & 0x0fff;
+        let mut expected = mask.iter_bits().powerset()
+            .map(|bits| bits.iter().map(|&i| 1 << i).sum::<u64>())
+            .sorted()
+            .collect_vec()
+            .into_iter();
+        let mut subsets = itertools::subsets_of_mask(mask);
+        for front in ends {
+            let (a, b) = if front {
+                (subsets.next(), expected.next())
+            } else {
+                (subsets.next_back(), expected.next_back())
+            };
+            if a != b || subsets.size_hint() != expected.size_hint() {
+                return false;
+            }
+        }
+        itertools::equal(subsets, expected)
+    }
+
+    fn masks_with_popcount_match_combinations(n: u8, k: u8, ends: Vec<bool>) -> bool {
+        let (n, k) = (n as usize % 11, k as usize % 11);
+        let mut expected = (0..n).combinations(k)
+            .map(|bits| bits.iter().map(|&i| 1 << i).sum::<u64>())
+            .sorted()
+            .collect_vec()
+            .into_iter();
+        let mut masks = itertools::masks_with_popcount(n, k);
+        for front in ends {
+            let (a, b) = if front {
+                (masks.next(), expected.next())
+            } else {
+                (masks.next_back(), expected.next_back())
+            };
+            if a != b || masks.size_hint() != expected.size_hint() {
+                return false;
+            }
+        }
+        itertools::equal(masks, expected)
+    }
+
     fn permutations_k0_yields_once(n: usize) -> () {
         let k = 0;
         let expected: Vec<Vec<usize>> = vec![vec![]];
@@ -909,6 +1069,146 @@ quickcheck! {
 }
 
 quickcheck! {
+    fn size_powerset_range(it: Iter<u8, Exact>, min: u8, max: u8) -> bool {
+        correct_size_hint(it.take(12).powerset_range(min as usize % 8..=max as usize % 14))
+    }
+
+    fn size_powerset_gray(it: Iter<u8, Exact>) -> bool {
+        correct_size_hint(it.take(12).powerset_gray())
+    }
+
+    fn size_set_partitions(it: Iter<u8, Exact>, k: usize) -> bool {
+        correct_size_hint(it.clone().take(7).set_partitions()) &&
+            correct_size_hint(it.take(7).set_partitions_k(k % 8))
+    }
+
+    fn size_combinations_k(it: Iter<u8>, k: usize) -> bool {
+        correct_size_hint(it.take(12).combinations(k % 6))
+    }
+
+    fn exact_size_combinations(n: u8, k: usize) -> bool {
+        exact_size((0..n % 14).combinations(k % 8))
+    }
+
+    fn combinations_count(n: u8, k: usize) -> bool {
+        let n = n % 10;
+        correct_count(|| (0..n).combinations(k % 6))
+    }
+
+    fn size_combinations_with_replacement(it: Iter<u8>, k: usize) -> bool {
+        correct_size_hint(it.take(8).combinations_with_replacement(k % 5))
+    }
+
+    fn exact_size_combinations_with_replacement(n: u8, k: usize) -> bool {
+        exact_size((0..n % 10).combinations_with_replacement(k % 6))
+    }
+
+    fn combinations_with_replacement_count(n: u8, k: usize) -> bool {
+        let n = n % 8;
+        correct_count(|| (0..n).combinations_with_replacement(k % 5))
+    }
+
+    fn exact_size_powerset(n: u8, min: u8, max: u8) -> bool {
+        let n = n % 10;
+        exact_size((0..n).powerset()) &&
+            exact_size((0..n).powerset_range(min as usize % 8..max as usize % 12))
+    }
+
+    fn powerset_count(n: u8, min: u8, max: u8) -> bool {
+        let n = n % 8;
+        correct_count(|| (0..n).powerset()) &&
+            correct_count(|| (0..n).powerset_range(min as usize % 8..=max as usize % 12))
+    }
+
+    fn size_cartesian_power(it: Iter<u8>, n: usize) -> bool {
+        correct_size_hint(it.clone().take(5).cartesian_power::<3>()) &&
+            correct_size_hint(it.take(5).cartesian_power_vec(n % 4))
+    }
+
+    fn exact_size_cartesian_power(n: u8, k: usize) -> bool {
+        exact_size((0..n % 6).cartesian_power::<3>()) &&
+            exact_size((0..n % 6).cartesian_power_vec(k % 4))
+    }
+
+    fn cartesian_power_count(n: u8, k: usize) -> bool {
+        let n = n % 5;
+        correct_count(|| (0..n).cartesian_power_vec(k % 4))
+    }
+
+    fn size_diagonal_product(a: Iter<u16>, b: Iter<i16>) -> bool {
+        correct_size_hint(a.diagonal_product(b))
+    }
+
+    fn diagonal_product_covers_product(a: Vec<u8>, b: Vec<u8>) -> bool {
+        let mut diagonal = a.iter().diagonal_product(&b).collect_vec();
+        let mut product = a.iter().cartesian_product(&b).collect_vec();
+        diagonal.sort();
+        product.sort();
+        diagonal == product
+    }
+
+    fn multi_diagonal_product_covers_product(a: ShiftRange) -> bool {
+        let mut diagonal = a.clone().multi_diagonal_product().collect_vec();
+        let mut product = a.clone().multi_cartesian_product().collect_vec();
+        diagonal.sort();
+        product.sort();
+        correct_size_hint(a.multi_diagonal_product()) && diagonal == product
+    }
+
+    fn combinations_pruned_filters_prefixes(v: Vec<u8>, k: usize, m: u8) -> bool {
+        let k = k % 5;
+        let m = m % 7 + 1;
+        let accept = |prefix: &[u8]| prefix.iter().map(|&x| x as usize).sum::<usize>() % m as usize != 1;
+        let pruned = v.iter().copied().take(9).combinations_pruned(k, accept).collect_vec();
+        let filtered = v.iter().copied().take(9).combinations(k)
+            .filter(|c| (1..=k).all(|len| accept(&c[..len])))
+            .collect_vec();
+        pruned == filtered
+    }
+
+    fn size_combinations_pruned(it: Iter<u16>, k: usize) -> bool {
+        correct_size_hint(it.combinations_pruned(k % 4, |prefix| prefix[0] % 3 != 0))
+    }
+
+    fn split_at_combinatorics(n: u8, k: u8, skip: u8, index: u8) -> bool {
+        let (n, k, skip, index) = (n as usize % 6, k as usize % 4, skip as usize % 8, index as usize % 32);
+
+        let combs = || (0..n).combinations(k).dropping(skip);
+        let (front, back) = combs().split_at(index);
+        let split_combs = front.len() == index.min(combs().len()) &&
+            front.chain(back).eq(combs());
+
+        let perms = || (0..n).permutations(k).dropping(skip);
+        let (front, back) = perms().split_at(index);
+        let split_perms = front.chain(back).eq(perms());
+
+        let powerset = || (0..n).powerset().dropping(skip);
+        let (front, back) = powerset().split_at(index);
+        let split_powerset = front.chain(back).eq(powerset());
+
+        let product = || (0..k).map(|i| 0..(n + i) % 4).multi_cartesian_product().dropping(skip);
+        let (front, back) = product().split_at(index);
+        let split_product = front.chain(back).eq(product());
+
+        split_combs && split_perms && split_powerset && split_product
+    }
+
+    fn split_into_combinatorics(n: u8, k: u8, parts: u8) -> bool {
+        let (n, k, parts) = (n as usize % 7, k as usize % 4, parts as usize % 9 + 1);
+        let balanced = |lens: Vec<usize>| lens.len() == parts &&
+            lens.iter().max().unwrap() - lens.iter().min().unwrap() <= 1;
+
+        let combs = (0..n).combinations(k).split_into(parts);
+        let perms = (0..n).permutations(k).split_into(parts);
+        let powerset = (0..n).powerset().split_into(parts);
+        balanced(combs.iter().map(|p| p.len()).collect()) &&
+            balanced(perms.iter().map(|p| p.size_hint().0).collect()) &&
+            balanced(powerset.iter().map(|p| p.len()).collect()) &&
+            combs.into_iter().flatten().eq((0..n).combinations(k)) &&
+            perms.into_iter().flatten().eq((0..n).permutations(k)) &&
+            powerset.into_iter().flatten().eq((0..n).powerset())
+    }
+
     fn size_powerset(it: Iter<u8, Exact>) -> bool {
         // Powerset cardinality gets large very quickly, limit input to keep test fast.
         correct_size_hint(it.take(12).powerset())
@@ -931,6 +1231,66 @@ quickcheck! {
         let rest_count = iter.count();
         assert_eq!(answer, first_count + rest_count);
     }
+
+    fn size_duplicates(it: Iter<i8>) -> bool {
+        correct_size_hint(it.duplicates())
+    }
+
+    fn count_duplicates(it: Vec<i8>, take_first: u8) -> () {
+        let answer = it.iter().counts().values().filter(|&&n| n > 1).count();
+        let mut iter = cloned(&it).duplicates();
+        let first_count = (&mut iter).take(take_first as usize).count();
+        let rest_count = iter.count();
+        assert_eq!(answer, first_count + rest_count);
+    }
+
+    fn correct_duplicates(it: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        // Each element whose key was seen exactly once before
+        let expected = it.iter().enumerate()
+            .filter(|&(i, x)| it[..i].iter().filter(|&y| y % modulo == x % modulo).count() == 1)
+            .map(|(_, x)| x)
+            .collect_vec();
+        assert_eq!(it.iter().duplicates_by(|&x| x % modulo).collect_vec(), expected);
+        assert_eq!(it.iter().duplicates_by(|&x| x % modulo).count(), expected.len());
+        assert_eq!(it.iter().duplicates().collect_vec(),
+                   it.iter().duplicates_by(|&x| *x).collect_vec());
+        assert_eq!(it.iter().duplicates_with_count().sorted().collect_vec(),
+                   it.iter().counts().into_iter().filter(|&(_, n)| n > 1).sorted().collect_vec());
+    }
+
+    fn size_unique_recent(it: Iter<i8>, n: u8) -> bool {
+        correct_size_hint(it.unique_recent(n as usize % 8))
+    }
+
+    fn correct_unique_recent(it: Vec<u8>, n: u8) -> () {
+        // Keep the recent elements from the least to the most recent
+        let n = n as usize % 8;
+        let mut recent = Vec::new();
+        let expected = it.iter().filter(|&x| {
+            let seen = recent.iter().position(|&y| y == *x).map(|i| recent.remove(i));
+            recent.push(*x);
+            if recent.len() > n {
+                recent.remove(0);
+            }
+            seen.is_none()
+        }).collect_vec();
+        assert_eq!(it.iter().unique_recent(n).collect_vec(), expected);
+    }
+
+    fn size_unique_approx(it: Iter<i8>, bits: u8) -> bool {
+        correct_size_hint(it.unique_approx(bits as usize + 1, 3))
+    }
+
+    fn correct_unique_approx(it: Vec<u8>, bits: u16, hashes: u8) -> () {
+        // No duplicates, only missed elements with too small a filter
+        let hashes = hashes as u32 % 8 + 1;
+        let unique = it.iter().unique().collect_vec();
+        let approx = it.iter().unique_approx(bits as usize + 1, hashes).collect_vec();
+        let mut rest = unique.iter();
+        assert!(approx.iter().all(|x| rest.any(|y| y == x)));
+        assert_eq!(it.iter().unique_approx(1 << 20, 8).collect_vec(), unique);
+    }
 }
 
 quickcheck! {
@@ -992,6 +1352,65 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn fuzz_group_by_buffer_limit(data: Vec<u8>, limit: u8, order: Vec<bool>) -> bool {
+        use itertools::BufferLimitPolicy;
+
+        // Keep some groups for later, so that they are buffered
+        let limit = limit as usize % 8;
+        let grouper = data.iter().group_by(|k| *k / 3).with_buffer_limit(limit, BufferLimitPolicy::Discard);
+        let mut runs = Vec::new();
+        let mut old_groups = Vec::new();
+        for ((_, group), &keep) in grouper.into_iter().zip(order.iter().chain(std::iter::repeat(&false))) {
+            if keep {
+                old_groups.push(group);
+            } else {
+                runs.push(group.collect_vec());
+            }
+            assert!(grouper.buffer_stats().buffered <= limit);
+        }
+        runs.extend(old_groups.into_iter().map(|group| group.collect_vec()));
+        assert!(grouper.buffer_stats().peak_buffered <= limit);
+
+        // Each group lost at most a tail of its elements
+        let expected = data.iter().group_by(|k| *k / 3).into_iter().map(|(_, group)| group.collect_vec()).collect_vec();
+        runs.sort();
+        let lost = expected.iter().filter(|run| !runs.contains(run)).count();
+        lost <= grouper.buffer_stats().discarded_groups &&
+            runs.iter().all(|run| expected.iter().any(|full| full.starts_with(run)))
+    }
+}
+
+quickcheck! {
+    fn fuzz_group_by_sync(data: Vec<u8>, order: Vec<(bool, bool)>) -> bool {
+        // Consume some groups at once, and keep the others to consume them
+        // later, in some shuffled order
+        let mut groups = data.iter().group_by_sync(|k| *k / 3).enumerate();
+        let mut runs = Vec::new();
+        let mut old_groups = Vec::new();
+        for &(keep, shuffle) in &order {
+            match groups.next() {
+                Some((i, (_, gr))) => if keep {
+                    old_groups.push((i, gr));
+                    if shuffle {
+                        old_groups.reverse();
+                    }
+                } else {
+                    runs.push((i, gr.collect_vec()));
+                },
+                None => break,
+            }
+        }
+        runs.extend(old_groups.into_iter().map(|(i, gr)| (i, gr.collect_vec())));
+        runs.extend(groups.map(|(i, (_, gr))| (i, gr.collect_vec())));
+        runs.sort_by_key(|&(i, _)| i);
+
+        let grouper = data.iter().group_by(|k| *k / 3);
+        itertools::equal(runs.into_iter().map(|(_, run)| run),
+                         grouper.into_iter().map(|(_, gr)| gr.collect_vec()))
+    }
+}
+
 quickcheck! {
     fn equal_chunks_lazy(a: Vec<u8>, size: u8) -> bool {
         let mut size = size;
@@ -1009,6 +1428,41 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn equal_chunk_by_group_by(a: Vec<u8>, modulo: u8) -> bool {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let groups = a.iter().group_by(|&&x| x / modulo);
+        let expected = groups.into_iter().map(|(key, group)| (key, group.collect_vec())).collect_vec();
+        let mut it = a.iter().chunk_by(|&&x| x / modulo);
+        // Resume with `fold` after a first run
+        let first = it.next();
+        let rest = it.fold(Vec::new(), |mut acc, run| { acc.push(run); acc });
+        first.into_iter().chain(rest).collect_vec() == expected
+    }
+
+    fn chunk_by_size_hint(it: Iter<u8>) -> bool {
+        correct_size_hint(it.chunk_by(|&x| x / 10))
+    }
+
+    fn equal_chunks_vec(a: Vec<u8>, size: u8) -> bool {
+        let size = if size == 0 { 1 } else { size as usize };
+        let mut it = a.iter().chunks_vec(size);
+        let first = it.next();
+        let rest = it.fold(Vec::new(), |mut acc, chunk| { acc.push(chunk); acc });
+        itertools::equal(first.into_iter().chain(rest), a.chunks(size).map(|chunk| chunk.iter().collect_vec()))
+    }
+
+    fn chunks_vec_size_hint(it: Iter<u8>, size: u8) -> bool {
+        let size = if size == 0 { 1 } else { size as usize };
+        correct_size_hint(it.chunks_vec(size))
+    }
+
+    fn exact_size_chunks_vec(a: Vec<u8>, size: u8) -> bool {
+        let size = if size == 0 { 1 } else { size as usize };
+        exact_size(a.iter().chunks_vec(size))
+    }
+}
+
 quickcheck! {
     fn equal_tuple_windows_1(a: Vec<u8>) -> bool {
         let x = a.windows(1).map(|s| (&s[0], ));
@@ -1507,6 +1961,260 @@ quickcheck! {
         }
     }
 
+    fn correct_grouping_map_by_count_first_last_modulo_key(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let count = a.iter().copied().into_grouping_map_by(|i| i % modulo).count();
+        let first = a.iter().copied().into_grouping_map_by(|i| i % modulo).first();
+        let last = a.iter().copied().into_grouping_map_by(|i| i % modulo).last();
+
+        let group_map_lookup = a.iter().copied().map(|i| (i % modulo, i)).into_group_map();
+        assert_eq!(count.len(), group_map_lookup.len());
+        for (key, vals) in group_map_lookup {
+            assert_eq!(count[&key], vals.len());
+            assert_eq!(first[&key], vals[0]);
+            assert_eq!(last[&key], vals[vals.len() - 1]);
+        }
+    }
+
+    fn correct_grouping_map_by_k_smallest_k_largest_modulo_key(a: Vec<(u8, u16)>, modulo: u8, k: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let k = k as usize % 5;
+        // Compare by the first component only, to check which of equal elements are kept
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        struct ByFirst(u8, u16);
+        impl PartialOrd for ByFirst {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+        }
+        impl Ord for ByFirst {
+            fn cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+        }
+
+        let values = || a.iter().map(|&(x, y)| ByFirst(x, y));
+        let smallest = values().into_grouping_map_by(|v| v.0 % modulo).k_smallest(k);
+        let largest = values().into_grouping_map_by(|v| v.0 % modulo).k_largest(k);
+
+        let group_map_lookup = values().map(|v| (v.0 % modulo, v)).into_group_map();
+        assert_eq!(smallest.len(), group_map_lookup.len());
+        assert_eq!(largest.len(), group_map_lookup.len());
+        for (key, vals) in group_map_lookup {
+            // Stable sorts keep equal elements in their original order
+            let mut sorted = vals.clone();
+            sorted.sort();
+            sorted.truncate(k);
+            assert_eq!(smallest[&key], sorted);
+            let mut sorted = vals;
+            sorted.sort_by(|x, y| y.cmp(x));
+            sorted.truncate(k);
+            assert_eq!(largest[&key], sorted);
+        }
+    }
+
+    fn correct_grouping_map_by_mean_sorted_collect_modulo_key(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let mean = a.iter().copied().into_grouping_map_by(|i| i % modulo).mean();
+        let sorted = a.iter().copied().into_grouping_map_by(|i| i % modulo).sorted_collect();
+
+        let group_map_lookup = a.iter().copied().map(|i| (i % modulo, i)).into_group_map();
+        assert_eq!(mean.len(), group_map_lookup.len());
+        for (key, vals) in group_map_lookup {
+            let sum = vals.iter().map(|&v| v as u64).sum::<u64>();
+            assert_eq!(mean[&key].sum(), sum as f64);
+            assert_eq!(mean[&key].count(), vals.len());
+            assert_eq!(mean[&key].mean(), sum as f64 / vals.len() as f64);
+            assert_eq!(sorted[&key], vals.into_iter().sorted().collect_vec());
+        }
+    }
+
+    fn correct_grouping_map_by_aggregate_many_modulo_key(a: Vec<u8>, modulo: u8) -> () {
+        use itertools::aggregations::{Count, First, Last, Max, Mean, Min, Product, Sum};
+
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let values = || a.iter().map(|&b| Wrapping(b as u64)); // Avoid overflows
+        let lookup = values()
+            .into_grouping_map_by(|i| i.0 % modulo as u64)
+            .aggregate_many((Count, First, Last, Sum, Product, Min, Max));
+        let mean = a.iter().copied().into_grouping_map_by(|i| i % modulo).aggregate_many(Mean);
+
+        let grouping_map = || values().into_grouping_map_by(|i| i.0 % modulo as u64);
+        let (count, first, last, sum, product, min, max) = (
+            grouping_map().count(), grouping_map().first(), grouping_map().last(),
+            grouping_map().sum(), grouping_map().product(), grouping_map().min(), grouping_map().max()
+        );
+        assert_eq!(lookup.len(), count.len());
+        for (key, &aggregated) in lookup.iter() {
+            assert_eq!(aggregated, (count[key], first[key], last[key], sum[key], product[key], min[key], max[key]));
+        }
+        assert_eq!(mean, a.iter().copied().into_grouping_map_by(|i| i % modulo).mean());
+    }
+
+    fn correct_grouping_map_in_map_kinds(a: Vec<u8>, modulo: u8) -> () {
+        use itertools::{BTreeMapKind, HashMapKind};
+        use std::collections::{hash_map::DefaultHasher, BTreeMap};
+        use std::hash::BuildHasherDefault;
+
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let lookup = a.iter().copied().into_grouping_map_by(|i| i % modulo).minmax();
+
+        let ordered = a.iter().copied().into_grouping_map_by_in(BTreeMapKind, |i| i % modulo).minmax();
+        assert!(ordered.keys().tuple_windows().all(|(k1, k2)| k1 < k2));
+        assert_eq!(ordered, lookup.clone().into_iter().collect::<BTreeMap<_, _>>());
+
+        let kind = HashMapKind::<BuildHasherDefault<DefaultHasher>>::default();
+        let hashed = a.iter().copied().map(|i| (i % modulo, i)).into_grouping_map_in(kind).minmax();
+        assert_eq!(hashed.into_iter().collect::<HashMap<_, _>>(), lookup);
+    }
+
+    fn correct_group_map_in_map_kinds(a: Vec<u8>, modulo: u8) -> () {
+        use itertools::BTreeMapKind;
+        use std::collections::BTreeMap;
+
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let lookup = a.iter().copied().into_group_map_by(|i| i % modulo);
+        let ordered = a.iter().copied().into_group_map_by_in(BTreeMapKind, |i| i % modulo);
+        assert_eq!(ordered, lookup.into_iter().collect::<BTreeMap<_, _>>());
+        assert_eq!(ordered, a.iter().copied().map(|i| (i % modulo, i)).into_group_map_in(BTreeMapKind));
+    }
+
+    fn correct_grouping_map_then_by_modulo_keys(a: Vec<u8>, m1: u8, m2: u8) -> () {
+        use itertools::BTreeMapKind;
+
+        let m1 = if m1 == 0 { 1 } else { m1 }; // Avoid `% 0`
+        let m2 = if m2 == 0 { 1 } else { m2 };
+        let lookup = a.iter().map(|&i| i as u32).into_grouping_map_by(|i| i % m1 as u32)
+            .then_by(|i| i % m2 as u32)
+            .sum();
+        let expected = a.iter().map(|&i| i as u32).into_group_map_by(|i| i % m1 as u32).into_iter()
+            .map(|(k1, group)| {
+                let inner = group.into_iter().into_grouping_map_by(|i| i % m2 as u32).sum();
+                (k1, inner)
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(lookup, expected);
+
+        // A third level, in order
+        let ordered = a.iter().copied()
+            .into_grouping_map_by_in(BTreeMapKind, |i| i % m1)
+            .then_by(|i| i % m2)
+            .then_by(|&i| i)
+            .count();
+        for (k1, middle) in &ordered {
+            for (k2, inner) in middle {
+                for (&i, &count) in inner {
+                    assert_eq!((i % m1, i % m2), (*k1, *k2));
+                    assert_eq!(count, a.iter().filter(|&&j| j == i).count());
+                }
+            }
+        }
+        assert_eq!(ordered.values().flat_map(|middle| middle.values()).map(|inner| inner.len()).sum::<usize>(),
+                   a.iter().unique().count());
+
+        // Discarded inner groups leave no empty maps behind
+        let odd_sums = a.iter().copied()
+            .into_grouping_map_by(|i| i % m1)
+            .then_by(|i| i % m2)
+            .aggregate(|acc, _, val| {
+                let acc = acc.unwrap_or(0u32) + val as u32;
+                if acc % 2 == 1 { Some(acc) } else { None }
+            });
+        assert!(odd_sums.values().all(|inner| !inner.is_empty() && inner.values().all(|&acc| acc % 2 == 1)));
+    }
+
+    fn correct_nested_group_map_by_modulo_keys(a: Vec<u8>, m1: u8, m2: u8) -> () {
+        use itertools::BTreeMapKind;
+        use std::collections::BTreeMap;
+
+        let m1 = if m1 == 0 { 1 } else { m1 }; // Avoid `% 0`
+        let m2 = if m2 == 0 { 1 } else { m2 };
+        let lookup = a.iter().copied().into_nested_group_map_by((|i: &u8| i % m1, |i: &u8| i % m2));
+        let expected = a.iter().copied().into_group_map_by(|i| i % m1).into_iter()
+            .map(|(k1, group)| (k1, group.into_iter().into_group_map_by(|i| i % m2)))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(lookup, expected);
+
+        let ordered = a.iter().copied()
+            .into_nested_group_map_by_in(BTreeMapKind, (|i: &u8| i % m1, |i: &u8| i % m2, |&i: &u8| i));
+        let expected = a.iter().copied().into_group_map_by(|i| (i % m1, i % m2, *i)).into_iter()
+            .fold(BTreeMap::<_, BTreeMap<_, BTreeMap<_, _>>>::new(), |mut map, ((k1, k2, k3), group)| {
+                map.entry(k1).or_default().entry(k2).or_default().insert(k3, group);
+                map
+            });
+        assert_eq!(ordered, expected);
+    }
+
+    fn correct_ordered_group_maps(a: Vec<u8>, modulo: u8) -> () {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        let keys = a.iter().map(|&x| x % modulo).unique().collect_vec();
+        let lookup = a.iter().into_ordered_group_map_by(|&x| x % modulo);
+        assert_eq!(lookup.keys().cloned().collect_vec(), keys);
+        let expected = a.iter().into_group_map_by(|&x| x % modulo);
+        assert!(lookup.iter().all(|(key, group)| expected[key] == *group));
+
+        // Discarding an accumulator sends its key last
+        let lookup = a.iter()
+            .into_grouping_map_by(|&x| x % modulo)
+            .ordered()
+            .aggregate(|acc, _key, &x| if x % 4 == 0 { None } else { Some(acc.unwrap_or(0u32) + x as u32) });
+        let mut keys = Vec::new();
+        for &x in &a {
+            let key = x % modulo;
+            if x % 4 == 0 {
+                keys.retain(|&k| k != key);
+            } else if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        assert_eq!(lookup.keys().cloned().collect_vec(), keys);
+    }
+
+    fn correct_ordered_map(ops: Vec<(bool, u8, u8)>) -> () {
+        use std::hash::{BuildHasherDefault, Hasher};
+        use itertools::OrderedMap;
+
+        // Few hashes, so that keys collide
+        #[derive(Default)]
+        struct FewHashes(u64);
+
+        impl Hasher for FewHashes {
+            fn finish(&self) -> u64 { self.0 % 3 }
+            fn write(&mut self, bytes: &[u8]) {
+                bytes.iter().for_each(|&b| self.0 += b as u64)
+            }
+        }
+
+        let mut map = OrderedMap::with_hasher(BuildHasherDefault::<FewHashes>::default());
+        let mut model: Vec<(u8, u8)> = Vec::new();
+        for (insert, key, value) in ops {
+            let key = key % 16;
+            let position = model.iter().position(|&(k, _)| k == key);
+            if insert {
+                let old = match position {
+                    Some(i) => Some(std::mem::replace(&mut model[i].1, value)),
+                    None => { model.push((key, value)); None }
+                };
+                assert_eq!(map.insert(key, value), old);
+            } else {
+                assert_eq!(map.remove(&key), position.map(|i| model.remove(i).1));
+            }
+            assert_eq!(map.iter().map(|(&k, &v)| (k, v)).collect_vec(), model);
+            assert!(model.iter().all(|(k, v)| map.get(k) == Some(v)));
+        }
+    }
+
+    fn correct_unique_and_counts_in_map_kinds(a: Vec<u8>, modulo: u8) -> () {
+        use itertools::BTreeMapKind;
+        use std::collections::BTreeMap;
+
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        assert_eq!(a.iter().unique_in(BTreeMapKind).collect_vec(), a.iter().unique().collect_vec());
+        assert_eq!(a.iter().rev().unique_in(BTreeMapKind).collect_vec(), a.iter().rev().unique().collect_vec());
+        assert_eq!(a.iter().unique_in(BTreeMapKind).count(), a.iter().unique().count());
+        assert_eq!(a.iter().unique_by_in(BTreeMapKind, |&i| i % modulo).collect_vec(),
+                   a.iter().unique_by(|&i| i % modulo).collect_vec());
+        assert_eq!(a.iter().unique_by_in(BTreeMapKind, |&i| i % modulo).count(),
+                   a.iter().unique_by(|&i| i % modulo).count());
+        assert_eq!(a.iter().counts_in(BTreeMapKind), a.iter().counts().into_iter().collect::<BTreeMap<_, _>>());
+    }
+
     // This should check that if multiple elements are equally minimum or maximum
     // then `max`, `min` and `minmax` pick the first minimum and the last maximum.
     // This is to be consistent with `std::iter::max` and `std::iter::min`.
@@ -1554,6 +2262,44 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn counts_by_modulo(nums: Vec<u8>, modulo: u8) -> bool {
+        let modulo = if modulo == 0 { 1 } else { modulo }; // Avoid `% 0`
+        nums.iter().counts_by(|&x| x % modulo) == nums.iter().map(|&x| x % modulo).counts()
+    }
+
+    fn correct_most_least_common(nums: Vec<u8>, other: Vec<u8>, k: u8) -> () {
+        use itertools::BTreeMapKind;
+        use std::cmp::Reverse;
+
+        let k = k as usize % 8;
+        let mut counts = nums.iter().into_counts_in(BTreeMapKind);
+        counts.merge(other.iter().into_counts());
+        let expected = nums.iter().chain(&other).counts();
+        assert_eq!(counts.total(), nums.len() + other.len());
+        assert_eq!(counts.len(), expected.len());
+        for (item, &n) in &expected {
+            assert_eq!(counts.get(item), n);
+        }
+
+        // Sorting is stable, so equal counts stay in the order of the items
+        let mut by_count = expected.into_iter().sorted().collect_vec();
+        by_count.sort_by_key(|&(_, n)| n);
+        let least = by_count.iter().take(k).cloned().collect_vec();
+        by_count.sort_by_key(|&(_, n)| Reverse(n));
+        let most = by_count.iter().take(k).cloned().collect_vec();
+        assert_eq!(counts.least_common(k).into_iter().map(|(&item, n)| (item, n)).collect_vec(), least);
+        assert_eq!(counts.most_common(k).into_iter().map(|(&item, n)| (item, n)).collect_vec(), most);
+
+        // The unordered table picks the same counts
+        let counts = nums.iter().into_counts();
+        let counts_of = |v: Vec<(&&u8, usize)>| v.into_iter().map(|(_, n)| n).collect_vec();
+        let ordered = nums.iter().into_counts_in(BTreeMapKind);
+        assert_eq!(counts_of(counts.most_common(k)), counts_of(ordered.most_common(k)));
+        assert_eq!(counts_of(counts.least_common(k)), counts_of(ordered.least_common(k)));
+    }
+}
+
 quickcheck! {
     fn test_double_ended_zip_2(a: Vec<u8>, b: Vec<u8>) -> TestResult {
         let mut x =
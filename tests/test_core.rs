@@ -270,3 +270,49 @@ fn product1() {
     assert_eq!(v[1..3].iter().cloned().product1::<i32>(), Some(2));
     assert_eq!(v[1..5].iter().cloned().product1::<i32>(), Some(24));
 }
+
+#[test]
+fn for_each_permutation() {
+    let mut items = [1, 2, 3, 4];
+    let mut count = 0;
+    let mut sum = 0;
+    it::for_each_permutation(&mut items, |p| {
+        count += 1;
+        sum += p[0];
+    });
+    assert_eq!(count, 24);
+    assert_eq!(sum, 6 * (1 + 2 + 3 + 4));
+
+    let mut count = 0;
+    it::for_each_permutation(&mut [(); 0], |_| count += 1);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn bitmasks() {
+    use crate::it::IterBits;
+
+    it::assert_equal(0u64.iter_bits(), None);
+    it::assert_equal(u64::MAX.iter_bits().rev().take(2), [63, 62].iter().cloned());
+    assert_eq!(0b1011_0010u32.iter_bits().len(), 4);
+
+    it::assert_equal(it::subsets_of_mask(0), Some(0));
+    let mut subsets = it::subsets_of_mask(0b1101);
+    assert_eq!(subsets.len(), 8);
+    assert_eq!(subsets.next(), Some(0b0000));
+    assert_eq!(subsets.next_back(), Some(0b1101));
+    assert_eq!(subsets.len(), 6);
+    it::assert_equal(subsets, [0b0001, 0b0100, 0b0101, 0b1000, 0b1001, 0b1100].iter().cloned());
+    assert_eq!(it::subsets_of_mask(u64::MAX >> 1).len(), 1 << 63);
+    assert_eq!(it::subsets_of_mask(u64::MAX).size_hint(), (usize::MAX, None));
+    assert_eq!(it::subsets_of_mask(u64::MAX).next_back(), Some(u64::MAX));
+
+    it::assert_equal(it::masks_with_popcount(3, 0), Some(0));
+    it::assert_equal(it::masks_with_popcount(0, 0), Some(0));
+    it::assert_equal(it::masks_with_popcount(2, 3), None);
+    it::assert_equal(it::masks_with_popcount(64, 0).rev(), Some(0));
+    it::assert_equal(it::masks_with_popcount(64, 64), Some(u64::MAX));
+    it::assert_equal(it::masks_with_popcount(64, 1).rev().take(2), [1 << 63, 1 << 62].iter().cloned());
+    assert_eq!(it::masks_with_popcount(64, 63).last(), Some(u64::MAX - 1));
+    assert_eq!(it::masks_with_popcount(64, 32).size_hint(), (1832624140942590534, Some(1832624140942590534)));
+}
@@ -87,6 +87,75 @@ fn unique() {
     it::assert_equal(ys_rev.iter(), xs.iter().unique().rev());
 }
 
+#[test]
+fn duplicates_by() {
+    let xs = ["aaa", "bbbbb", "aa", "ccc", "bbbb", "aaaaa", "cccc"];
+    let ys = ["aa", "bbbb", "cccc"];
+    it::assert_equal(ys.iter(), xs.iter().duplicates_by(|x| x[..2].to_string()));
+    it::assert_equal(ys.iter(), xs.iter().rev().duplicates_by(|x| x[..2].to_string()).rev());
+    let ys_rev = ["ccc", "aa", "bbbbb"];
+    it::assert_equal(ys_rev.iter(), xs.iter().duplicates_by(|x| x[..2].to_string()).rev());
+}
+
+#[test]
+fn duplicates() {
+    let xs = [0, 1, 2, 3, 2, 1, 3];
+    let ys = [2, 1, 3];
+    it::assert_equal(ys.iter(), xs.iter().duplicates());
+    it::assert_equal(ys.iter(), xs.iter().rev().duplicates().rev());
+    let ys_rev = [3, 2, 1];
+    it::assert_equal(ys_rev.iter(), xs.iter().duplicates().rev());
+
+    let xs = [0, 1, 0, 1];
+    let ys = [0, 1];
+    it::assert_equal(ys.iter(), xs.iter().duplicates());
+    assert_eq!(xs.iter().duplicates().count(), 2);
+    assert_eq!([0, 1].iter().duplicates().next(), None);
+}
+
+#[test]
+fn duplicates_with_count() {
+    let xs = [0, 1, 2, 3, 2, 1, 3, 3];
+    it::assert_equal(xs.iter().duplicates_with_count().sorted(), vec![(&1, 2), (&2, 2), (&3, 3)]);
+    assert_eq!([0, 1].iter().duplicates_with_count().next(), None);
+}
+
+#[test]
+fn unique_recent() {
+    let xs = [0, 1, 0, 2, 2, 2, 0, 3, 1, 1, 0];
+    it::assert_equal(xs.iter().unique_recent(1), [0, 1, 0, 2, 0, 3, 1, 0].iter());
+    it::assert_equal(xs.iter().unique_recent(2), [0, 1, 2, 3, 1, 0].iter());
+    it::assert_equal(xs.iter().unique_recent(4), xs.iter().unique());
+    it::assert_equal(xs.iter().unique_recent(0), xs.iter());
+    it::assert_equal(xs.iter().rev().unique_recent(2).rev(), xs.iter().unique_recent(2));
+}
+
+#[test]
+fn unique_approx() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    let xs = [0, 1, 2, 3, 2, 1, 3];
+    it::assert_equal(xs.iter().unique_approx(1 << 16, 8), xs.iter().unique());
+    it::assert_equal(xs.iter().rev().unique_approx(1 << 16, 8).rev(), xs.iter().unique());
+
+    // With a single bit, every element after the first looks like a duplicate
+    let mut iter = xs.iter().unique_approx(1, 3);
+    assert_eq!(iter.false_positive_rate(), 0.);
+    it::assert_equal(iter.by_ref(), [0].iter());
+    assert!(iter.false_positive_rate() > 0.8);
+
+    // A fixed hasher filters out the same elements every time
+    let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    let unique = (0..1000).unique_approx_with_hasher(10_000, 7, hasher.clone()).collect_vec();
+    it::assert_equal((0..1000).unique_approx_with_hasher(10_000, 7, hasher.clone()), unique.clone());
+    assert!(unique.len() > 980);
+
+    let mut iter = (0..1000).unique_approx_with_hasher(10_000, 7, hasher);
+    iter.by_ref().for_each(drop);
+    assert!((iter.false_positive_rate() - 0.0082).abs() < 0.0005);
+}
+
 #[test]
 fn intersperse() {
     let xs = ["a", "", "b", "c"];
@@ -760,6 +829,79 @@ fn group_by_lazy_3() {
     }
 }
 
+#[test]
+fn group_by_buffer_limit() {
+    use crate::it::{BufferLimitError, BufferLimitPolicy};
+
+    let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
+    let grouper = data.iter().group_by(|k| *k).with_buffer_limit(3, BufferLimitPolicy::Discard);
+    let mut groups = grouper.into_iter().map(|(_, group)| group).collect_vec();
+    // Every group holds its first element, the buffer held two 0s and one 1,
+    // and the rest of the later groups did not fit
+    it::assert_equal(&mut groups[0], &[0, 0, 0]);
+    it::assert_equal(&mut groups[1], &[1, 1]);
+    it::assert_equal(&mut groups[2], &[0]);
+    it::assert_equal(&mut groups[3], &[2]);
+    it::assert_equal(&mut groups[4], &[3]);
+    let stats = grouper.buffer_stats();
+    assert_eq!((stats.buffered, stats.peak_buffered, stats.discarded_groups), (0, 3, 3));
+
+    // Dropped groups free their share of the buffer
+    let grouper = data.iter().group_by(|k| *k).with_buffer_limit(2, BufferLimitPolicy::Panic);
+    let mut groups = grouper.into_iter();
+    let (_, first) = groups.next().unwrap();
+    let (_, second) = groups.next().unwrap();
+    assert_eq!(grouper.buffer_stats().buffered, 2);
+    drop(first);
+    assert_eq!(grouper.buffer_stats().buffered, 0);
+    drop(second);
+    assert_eq!(groups.map(|(_, group)| group.count()).sum::<usize>(), 6);
+
+    let chunker = data.iter().chunks(4).with_buffer_limit(2, BufferLimitPolicy::Error);
+    let mut chunks = chunker.into_iter().collect_vec();
+    assert_eq!(chunks[0].try_next(), Ok(Some(&0)));
+    let err: BufferLimitError = chunks[0].try_next().unwrap_err();
+    assert_eq!(err.limit(), 2);
+    assert_eq!(chunks[0].try_next(), Ok(None));
+    it::assert_equal(&mut chunks[2], &[2, 3, 3]);
+    assert_eq!(chunker.buffer_stats().discarded_groups, 2);
+}
+
+#[test]
+#[should_panic]
+fn group_by_buffer_limit_panic() {
+    use crate::it::BufferLimitPolicy;
+
+    let data = vec![0, 0, 0, 1, 1];
+    let grouper = data.iter().group_by(|k| *k).with_buffer_limit(1, BufferLimitPolicy::Panic);
+    let _groups = grouper.into_iter().collect_vec();
+}
+
+#[test]
+fn group_by_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
+    let mut groups = data.clone().into_iter().group_by_sync(|&k| k).collect_vec();
+    assert_send_sync(&groups);
+    assert_eq!(groups.iter().map(|&(key, _)| key).collect_vec(), vec![0, 1, 0, 2, 3]);
+
+    // Consume the groups out of order from other threads, dropping one unread
+    drop(groups.remove(3));
+    let workers = groups.into_iter().rev()
+        .map(|(_, group)| std::thread::spawn(move || group.collect_vec()))
+        .collect_vec();
+    let runs = workers.into_iter().map(|worker| worker.join().unwrap()).collect_vec();
+    assert_eq!(runs, vec![vec![3, 3], vec![0, 0], vec![1, 1], vec![0, 0, 0]]);
+
+    let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
+    let mut chunks = data.into_iter().chunks_sync(3).collect_vec();
+    assert_send_sync(&chunks);
+    chunks.reverse();
+    let chunks = chunks.into_iter().map(|chunk| chunk.collect_vec()).collect_vec();
+    assert_eq!(chunks, vec![vec![3, 3], vec![0, 2, 2], vec![1, 1, 0], vec![0, 0, 0]]);
+}
+
 #[test]
 fn chunks() {
     let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
@@ -775,6 +917,31 @@ fn chunks() {
     }
 }
 
+#[test]
+fn chunk_by() {
+    let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
+    let expected = vec![(0, vec![0, 0, 0]), (1, vec![1, 1]), (0, vec![0, 0]), (2, vec![2, 2]), (3, vec![3, 3])];
+    it::assert_equal(data.iter().copied().chunk_by(|&x| x), expected.clone());
+
+    // Owned, so it can be chained and folded
+    let runs = data.into_iter().chunk_by(|&x| x).map(|(key, run)| (key, run.len()));
+    assert_eq!(runs.fold(Vec::new(), |mut acc, run| { acc.push(run); acc }),
+               expected.into_iter().map(|(key, run)| (key, run.len())).collect::<Vec<_>>());
+
+    let mut empty = Vec::<u8>::new().into_iter().chunk_by(|&x| x);
+    assert_eq!(empty.size_hint(), (0, Some(0)));
+    assert_eq!(empty.next(), None);
+}
+
+#[test]
+fn chunks_vec() {
+    let data = vec![0, 0, 0, 1, 1, 0, 0, 2, 2, 3, 3];
+    let chunks = data.iter().chunks_vec(3);
+    assert_eq!(chunks.len(), 4);
+    it::assert_equal(chunks, vec![vec![&0, &0, &0], vec![&1, &1, &0], vec![&0, &2, &2], vec![&3, &3]]);
+    it::assert_equal((0..4).chunks_vec(usize::MAX), vec![vec![0, 1, 2, 3]]);
+}
+
 #[test]
 fn concat_empty() {
     let data: Vec<Vec<()>> = Vec::new();
@@ -816,6 +983,165 @@ fn combinations() {
     it::assert_equal((0..2).combinations(2), vec![vec![0, 1]]);
 }
 
+#[test]
+fn combinations_nth() {
+    let mut it = (0..6).combinations(3);
+    assert_eq!(it.next(), Some(vec![0, 1, 2]));
+    assert_eq!(it.nth(2), Some(vec![0, 1, 5]));
+    assert_eq!(it.nth(5), Some(vec![0, 4, 5]));
+    assert_eq!(it.next(), Some(vec![1, 2, 3]));
+    assert_eq!(it.nth(8), Some(vec![3, 4, 5]));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+
+    assert_eq!((0..6).combinations(3).nth(20), None);
+    assert_eq!((0..6).combinations(0).next(), Some(vec![]));
+    assert_eq!((0..6).combinations(0).nth(1), None);
+
+    // Only as much of an infinite source is read as stepping would.
+    assert_eq!((0..).combinations(2).nth(1_000_000), Some(vec![0, 1_000_001]));
+}
+
+#[test]
+fn combinations_rank_unrank() {
+    let mut it = (0..7).combinations(3);
+    for (rank, comb) in (0..7).combinations(3).enumerate() {
+        assert_eq!(it.rank(&comb), Some(rank));
+        assert_eq!(it.unrank(rank), Some(comb));
+    }
+    assert_eq!(it.unrank(35), None);
+    assert_eq!(it.rank(&[0, 1]), None);
+    assert_eq!(it.rank(&[0, 2, 2]), None);
+    assert_eq!(it.rank(&[0, 1, 7]), None);
+
+    let mut it = (0..100).combinations(50);
+    assert_eq!(it.unrank(0), None);
+    let mut it = (0..40).combinations(20);
+    let last = it.unrank(137_846_528_819);
+    assert_eq!(last, Some((20..40).collect()));
+    assert_eq!(it.rank(&last.unwrap()), Some(137_846_528_819));
+
+    // Near `usize::MAX` combinations, the coefficients still update exactly
+    let mut it = (0..66).combinations(33);
+    for &rank in &[0, 1, 3_609_714_217_008_132_869, 7_219_428_434_016_265_739] {
+        let comb = it.unrank(rank).unwrap();
+        assert_eq!(it.rank(&comb), Some(rank));
+    }
+    assert_eq!(it.unrank(7_219_428_434_016_265_739), Some((33..66).collect()));
+}
+
+#[test]
+fn array_combinations() {
+    it::assert_equal((1..5).array_combinations::<2>(), vec![
+        [1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4],
+    ]);
+    it::assert_equal((0..0).array_combinations::<0>(), vec![[]]);
+    it::assert_equal((0..3).array_combinations::<0>(), vec![[]]);
+    it::assert_equal((0..2).array_combinations::<3>(), <Vec<[_; 3]>>::new());
+
+    let mut it = (0..6).array_combinations::<3>();
+    assert_eq!(it.nth(3), Some([0, 1, 5]));
+    assert_eq!(it.nth(5), Some([0, 4, 5]));
+    assert_eq!(it.nth(9), Some([3, 4, 5]));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn combinations_size_hint() {
+    assert_eq!((0..64).combinations(32).size_hint(), (1_832_624_140_942_590_534, Some(1_832_624_140_942_590_534)));
+    assert_eq!((0..100).combinations(50).size_hint(), (usize::MAX, None));
+    assert_eq!((0..).combinations(2).size_hint(), (usize::MAX, None));
+    assert_eq!((0..10).filter(|_| true).combinations(2).size_hint(), (1, Some(45)));
+
+    let mut it = (0..100).combinations(99);
+    it.next();
+    assert_eq!(it.len(), 99);
+    assert_eq!(it.nth(98), Some((1..100).collect()));
+    assert_eq!(it.len(), 0);
+
+    assert_eq!((0..100).combinations_with_replacement(3).len(), 171_700);
+    assert_eq!((0..100).powerset().size_hint(), (usize::MAX, None));
+    assert_eq!((0..100).powerset_range(..=2).len(), 5051);
+}
+
+#[test]
+fn combinations_pruned() {
+    for k in 0..5 {
+        it::assert_equal((0..4).combinations_pruned(k, |_| true), (0..4).combinations(k));
+    }
+    it::assert_equal((0..3).combinations_pruned(1, |_| false), <Vec<Vec<_>>>::new());
+    it::assert_equal((0..3).combinations_pruned(0, |_| false), vec![vec![]]);
+
+    // Only accepted prefixes are ever extended.
+    let mut calls = 0;
+    let it = (0..10).combinations_pruned(3, |prefix| {
+        calls += 1;
+        prefix[0] == 0
+    });
+    assert_eq!(it.count(), 36);
+    // Eight possible first elements, eight second ones after 0, and the 36 results.
+    assert_eq!(calls, 8 + 8 + 36);
+
+    // Rejected branches are skipped, but still walk the rest of the source.
+    let mut it = (0..10).combinations_pruned(2, |prefix| prefix[prefix.len() - 1] < 3);
+    it::assert_equal(it.by_ref().take(3), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+    assert_eq!(it.size_hint(), (0, Some(35)));
+    assert_eq!(it.next(), None);
+
+    let it = (0..5).combinations_pruned(3, |prefix| prefix.len() < 3 || prefix[2] != 3);
+    assert_eq!(it.size_hint(), (0, Some(10)));
+    assert_eq!(it.count(), 7);
+}
+
+#[test]
+fn split_combinatorics() {
+    // Splitting a partly consumed iterator covers what is left of it.
+    let mut it = (0..5).combinations(3);
+    it.next();
+    let (front, back) = it.split_at(4);
+    assert_eq!(front.len(), 4);
+    it::assert_equal(front.chain(back), (0..5).combinations(3).skip(1));
+
+    let (front, back) = (0..3).combinations(2).split_at(10);
+    assert_eq!((front.count(), back.count()), (3, 0));
+
+    // Splitting a split half stays within its range.
+    let (front, _) = (0..6).combinations(2).split_at(10);
+    let (a, b) = front.split_at(3);
+    assert_eq!(b.len(), 7);
+    it::assert_equal(a.chain(b), (0..6).combinations(2).take(10));
+
+    let mut it = (0..4).permutations(4);
+    it.nth(5);
+    let parts = it.split_into(4);
+    let lens = parts.iter().map(|part| part.size_hint().0).collect_vec();
+    assert_eq!(lens, vec![5, 5, 4, 4]);
+    it::assert_equal(parts.into_iter().flatten(), (0..4).permutations(4).skip(6));
+
+    // The source may still be unread, or partly read, when splitting.
+    let mut it = (0..5).permutations(2);
+    it.next();
+    let (front, back) = it.split_at(7);
+    it::assert_equal(front.chain(back), (0..5).permutations(2).skip(1));
+    it::assert_equal((0..3).permutations(0).split_into(2).into_iter().flatten(), vec![vec![]]);
+
+    let mut it = (0..5).powerset_range(1..=3);
+    it.nth(2);
+    let (front, back) = it.split_at(12);
+    it::assert_equal(front.chain(back), (0..5).powerset_range(1..=3).skip(3));
+    it::assert_equal((0..0).powerset().split_into(3).into_iter().flatten(), vec![vec![]]);
+
+    let product = || vec![0..2, 0..0, 0..3].into_iter().multi_cartesian_product();
+    let (front, back) = product().split_at(1);
+    assert_eq!((front.count(), back.count()), (0, 0));
+    let product = || (0..3).map(|i| 0..i + 2).multi_cartesian_product();
+    let mut it = product();
+    it.nth(4);
+    let (front, back) = it.split_at(9);
+    assert_eq!(front.clone().last(), product().nth(13));
+    it::assert_equal(front.chain(back), product().skip(5));
+}
+
 #[test]
 fn combinations_of_too_short() {
     for i in 1..10 {
@@ -837,6 +1163,156 @@ fn permutations_zero() {
     it::assert_equal((0..0).permutations(0), vec![vec![]]);
 }
 
+#[test]
+fn permutation_swaps() {
+    it::assert_equal(it::permutation_swaps(0), vec![]);
+    it::assert_equal(it::permutation_swaps(1), vec![]);
+    it::assert_equal(it::permutation_swaps(2), vec![(0, 1)]);
+    assert_eq!(it::permutation_swaps(20).size_hint(), (2432902008176640000 - 1, Some(2432902008176640000 - 1)));
+    assert_eq!(it::permutation_swaps(21).size_hint(), (usize::MAX, None));
+
+    // Every swap leads to a new permutation, and they agree with the in-place version.
+    for n in 0..7 {
+        let mut items = (0..n).collect_vec();
+        let mut seen = vec![items.clone()];
+        for (i, j) in it::permutation_swaps(n) {
+            items.swap(i, j);
+            seen.push(items.clone());
+        }
+
+        let mut visited = Vec::new();
+        it::for_each_permutation(&mut (0..n).collect_vec(), |p| visited.push(p.to_vec()));
+        assert_eq!(seen, visited);
+
+        seen.sort();
+        it::assert_equal(seen, (0..n).permutations(n));
+    }
+}
+
+#[test]
+fn derangements() {
+    it::assert_equal((0..0).derangements(), vec![vec![]]);
+    it::assert_equal((0..1).derangements(), <Vec<Vec<_>>>::new());
+    it::assert_equal((0..2).derangements(), vec![vec![1, 0]]);
+    it::assert_equal((0..4).derangements(), (0..4).permutations(4)
+        .filter(|p| p.iter().enumerate().all(|(i, &x)| i != x)));
+
+    let counts = (0..10).map(|n| (0..n).derangements().count()).collect_vec();
+    assert_eq!(counts, vec![1, 0, 1, 2, 9, 44, 265, 1854, 14833, 133496]);
+    assert_eq!((0..20).derangements().size_hint(), (895014631192902121, Some(895014631192902121)));
+    assert_eq!((0..21).derangements().size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn necklaces() {
+    it::assert_equal((0..3).necklaces(0), vec![vec![]]);
+    it::assert_equal((0..0).necklaces(0), vec![vec![]]);
+    it::assert_equal((0..0).necklaces(2), <Vec<Vec<_>>>::new());
+    it::assert_equal((0..1).necklaces(3), vec![vec![0, 0, 0]]);
+    it::assert_equal((0..3).necklaces(1), vec![vec![0], vec![1], vec![2]]);
+
+    // OEIS A000031 and A000029: necklaces and bracelets with two colors.
+    let counts = (0..12).map(|k| (0..2).necklaces(k).count()).collect_vec();
+    assert_eq!(counts, vec![1, 2, 3, 4, 6, 8, 14, 20, 36, 60, 108, 188]);
+    let counts = (0..12).map(|k| (0..2).bracelets(k).count()).collect_vec();
+    assert_eq!(counts, vec![1, 2, 3, 4, 6, 8, 13, 18, 30, 46, 78, 126]);
+
+    // The first length where bracelets identify some necklaces over four colors.
+    assert_eq!((0..4).necklaces(3).count(), 24);
+    assert_eq!((0..4).bracelets(3).count(), 20);
+    assert_eq!((0..10).necklaces(19).size_hint(), (526315789473684220, Some(526315789473684220)));
+    assert_eq!((0..10).necklaces(30).size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn index_combinatorics() {
+    it::assert_equal(it::index_combinations(3, 0), vec![vec![]]);
+    it::assert_equal(it::index_combinations(2, 3), <Vec<Vec<_>>>::new());
+    it::assert_equal(it::index_permutations(0, 0).rev(), vec![vec![]]);
+    it::assert_equal(it::index_permutations(2, 3).rev(), <Vec<Vec<_>>>::new());
+    it::assert_equal(it::index_combinations_with_replacement(0, 0), vec![vec![]]);
+    it::assert_equal(it::index_combinations_with_replacement(0, 2), <Vec<Vec<_>>>::new());
+
+    for n in 0..6 {
+        for k in 0..6 {
+            it::assert_equal(it::index_combinations(n, k), (0..n).combinations(k));
+            it::assert_equal(it::index_permutations(n, k), (0..n).permutations(k));
+            it::assert_equal(it::index_combinations_with_replacement(n, k),
+                             (0..n).combinations_with_replacement(k));
+
+            let mut reversed = (0..n).combinations(k).collect_vec();
+            reversed.reverse();
+            it::assert_equal(it::index_combinations(n, k).rev(), reversed);
+            let mut reversed = (0..n).permutations(k).collect_vec();
+            reversed.reverse();
+            it::assert_equal(it::index_permutations(n, k).rev(), reversed);
+        }
+    }
+
+    // The array forms walk the same indices.
+    for n in 0..6 {
+        it::assert_equal(it::index_array_combinations::<3>(n).map(|a| a.to_vec()),
+                         it::index_combinations(n, 3));
+        it::assert_equal(it::index_array_permutations::<3>(n).rev().map(|a| a.to_vec()),
+                         it::index_permutations(n, 3).rev());
+        it::assert_equal(it::index_array_combinations_with_replacement::<3>(n).map(|a| a.to_vec()),
+                         it::index_combinations_with_replacement(n, 3));
+    }
+
+    // Both ends meet in the middle.
+    let mut iter = it::index_combinations(4, 2);
+    assert_eq!(iter.next(), Some(vec![0, 1]));
+    assert_eq!(iter.next_back(), Some(vec![2, 3]));
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    it::assert_equal(iter.rev(), vec![vec![1, 3], vec![1, 2], vec![0, 3], vec![0, 2]]);
+
+    assert_eq!(it::index_combinations(64, 32).size_hint(), (1832624140942590534, Some(1832624140942590534)));
+    assert_eq!(it::index_combinations(70, 35).size_hint(), (usize::MAX, None));
+    assert_eq!(it::index_permutations(20, 20).size_hint(), (2432902008176640000, Some(2432902008176640000)));
+    assert_eq!(it::index_permutations(30, 30).size_hint(), (usize::MAX, None));
+    let mut iter = it::index_permutations(30, 30);
+    iter.next_back();
+    assert_eq!(iter.next_back(), Some((0..30).rev().take(28).chain(vec![0, 1]).collect()));
+    assert_eq!(it::index_combinations_with_replacement(10, 3).count(), 220);
+
+    // Nothing is allocated in proportion to `n`.
+    let mut iter = it::index_permutations(usize::MAX, 2);
+    it::assert_equal(iter.by_ref().take(2), vec![vec![0, 1], vec![0, 2]]);
+    assert_eq!(iter.next_back(), Some(vec![usize::MAX - 1, usize::MAX - 2]));
+    assert_eq!(it::index_combinations_with_replacement(usize::MAX, 2).size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn multiset_permutations() {
+    it::assert_equal(vec![1, 2, 1].into_iter().multiset_permutations(3), vec![
+        vec![1, 1, 2],
+        vec![1, 2, 1],
+        vec![2, 1, 1],
+    ]);
+    it::assert_equal(vec![3, 1, 3, 2].into_iter().multiset_permutations(2), vec![
+        vec![1, 2],
+        vec![1, 3],
+        vec![2, 1],
+        vec![2, 3],
+        vec![3, 1],
+        vec![3, 2],
+        vec![3, 3],
+    ]);
+    it::assert_equal((0..0).multiset_permutations(0), vec![vec![]]);
+    it::assert_equal(vec![1, 1].into_iter().multiset_permutations(0), vec![vec![]]);
+    it::assert_equal(vec![1, 1].into_iter().multiset_permutations(3), <Vec<Vec<_>>>::new());
+
+    it::assert_equal(
+        "abca".chars().multiset_permutations_hash(2),
+        vec!["aa", "ab", "ac", "ba", "bc", "ca", "cb"].into_iter().map(|s| s.chars().collect_vec()),
+    );
+
+    let perms = vec![0; 30].into_iter().chain(vec![1; 30]).multiset_permutations(60);
+    assert_eq!(perms.size_hint(), (118_264_581_564_861_424, Some(118_264_581_564_861_424)));
+    let perms = (0..30).multiset_permutations(30);
+    assert_eq!(perms.size_hint(), (usize::MAX, None));
+}
+
 #[test]
 fn combinations_with_replacement() {
     // Pool smaller than n
@@ -887,6 +1363,182 @@ fn powerset() {
     assert_eq!((0..16).powerset().count(), 1 << 16);
 }
 
+#[test]
+fn powerset_range() {
+    it::assert_equal((0..3).powerset_range(1..=1), vec![vec![0], vec![1], vec![2]]);
+    it::assert_equal((0..3).powerset_range(2..), vec![
+        vec![0, 1], vec![0, 2], vec![1, 2],
+        vec![0, 1, 2],
+    ]);
+    it::assert_equal((0..3).powerset_range(..2), vec![
+        vec![],
+        vec![0], vec![1], vec![2],
+    ]);
+    it::assert_equal((0..3).powerset_range(4..), <Vec<Vec<_>>>::new());
+    it::assert_equal((0..3).powerset_range(2..2), <Vec<Vec<_>>>::new());
+    it::assert_equal((0..0).powerset_range(0..=0), vec![vec![]]);
+
+    let expected = (0..16).powerset().filter(|s| (4..=12).contains(&s.len())).count();
+    assert_eq!((0..16).powerset_range(4..=12).count(), expected);
+}
+
+#[test]
+fn powerset_gray() {
+    use it::SubsetChange::{Added, Removed};
+
+    assert_eq!((0..0).powerset_gray().next(), None);
+    it::assert_equal((0..1).powerset_gray(), vec![(Added(0), vec![0])]);
+
+    let mut current = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for (change, subset) in (0..8).powerset_gray() {
+        match change {
+            Added(x) => current.push(x),
+            Removed(x) => current.retain(|&y| y != x),
+        }
+        current.sort();
+        assert_eq!(current, subset);
+        assert!(seen.insert(subset));
+    }
+    assert_eq!(seen.len(), (1 << 8) - 1);
+
+    // The source is only read as far as needed.
+    assert_eq!((0..).powerset_gray().nth(6), Some((Removed(0), vec![2])));
+}
+
+#[test]
+fn set_partitions() {
+    it::assert_equal((0..0).set_partitions(), vec![<Vec<Vec<i32>>>::new()]);
+    it::assert_equal((0..1).set_partitions(), vec![vec![vec![0]]]);
+    it::assert_equal((0..2).set_partitions(), vec![vec![vec![0, 1]], vec![vec![0], vec![1]]]);
+
+    let bell = [1, 1, 2, 5, 15, 52, 203, 877, 4140];
+    for (n, &count) in bell.iter().enumerate() {
+        assert_eq!((0..n).set_partitions().count(), count);
+        assert_eq!((0..n).set_partitions().size_hint(), (count, Some(count)));
+    }
+
+    assert_eq!((0..25).set_partitions().size_hint(), (4_638_590_332_229_999_353, Some(4_638_590_332_229_999_353)));
+    assert_eq!((0..26).set_partitions().size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn set_partitions_k() {
+    it::assert_equal((0..0).set_partitions_k(0), vec![<Vec<Vec<i32>>>::new()]);
+    it::assert_equal((0..3).set_partitions_k(0), <Vec<Vec<Vec<_>>>>::new());
+    it::assert_equal((0..3).set_partitions_k(4), <Vec<Vec<Vec<_>>>>::new());
+    it::assert_equal((0..3).set_partitions_k(3), vec![vec![vec![0], vec![1], vec![2]]]);
+
+    // Stirling numbers of the second kind S(7, k)
+    let stirling = [0, 1, 63, 301, 350, 140, 21, 1];
+    for (k, &count) in stirling.iter().enumerate() {
+        let partitions = (0..7).set_partitions_k(k).collect_vec();
+        assert_eq!(partitions.len(), count);
+        assert!(partitions.iter().all(|p| p.len() == k));
+        assert_eq!((0..7).set_partitions_k(k).size_hint(), (count, Some(count)));
+    }
+    let all = (0..7).set_partitions().filter(|p| p.len() == 3).collect_vec();
+    it::assert_equal((0..7).set_partitions_k(3), all);
+
+    assert_eq!((0..1000).set_partitions_k(999).size_hint(), (499_500, Some(499_500)));
+}
+
+#[test]
+fn integer_partitions() {
+    it::assert_equal(it::integer_partitions(0), vec![<Vec<usize>>::new()]);
+    it::assert_equal(it::integer_partitions(1), vec![vec![1]]);
+    it::assert_equal(it::integer_partitions(5), vec![
+        vec![5],
+        vec![4, 1],
+        vec![3, 2],
+        vec![3, 1, 1],
+        vec![2, 2, 1],
+        vec![2, 1, 1, 1],
+        vec![1, 1, 1, 1, 1],
+    ]);
+
+    let counts = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42];
+    for (n, &count) in counts.iter().enumerate() {
+        assert_eq!(it::integer_partitions(n).size_hint(), (count, Some(count)));
+        assert!(it::integer_partitions(n).all(|p| p.iter().sum::<usize>() == n));
+    }
+    assert_eq!(it::integer_partitions(100).size_hint(), (190_569_292, Some(190_569_292)));
+    assert_eq!(it::integer_partitions(1_000_000).size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn cartesian_power() {
+    it::assert_equal((0..2).cartesian_power::<2>(), vec![[0, 0], [0, 1], [1, 0], [1, 1]]);
+    it::assert_equal((0..3).cartesian_power::<0>(), vec![[]]);
+    it::assert_equal((0..0).cartesian_power::<0>(), vec![[]]);
+    it::assert_equal((0..0).cartesian_power::<2>(), <Vec<[_; 2]>>::new());
+    it::assert_equal((0..1).cartesian_power::<3>(), vec![[0, 0, 0]]);
+
+    it::assert_equal((0..0).cartesian_power_vec(0), vec![vec![]]);
+    it::assert_equal((0..0).cartesian_power_vec(1), <Vec<Vec<_>>>::new());
+    for n in 1..4 {
+        let copies = (0..n).map(|_| 0..3);
+        it::assert_equal((0..3).cartesian_power_vec(n), copies.multi_cartesian_product());
+    }
+
+    // Only the last position walks an infinite source.
+    it::assert_equal((0..).cartesian_power::<2>().take(3), vec![[0, 0], [0, 1], [0, 2]]);
+
+    assert_eq!((0..10).cartesian_power::<19>().size_hint(), (10usize.pow(19), Some(10usize.pow(19))));
+    assert_eq!((0..10).cartesian_power::<20>().size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn diagonal_product() {
+    it::assert_equal((0..3).diagonal_product(0..2), vec![
+        (0, 0),
+        (0, 1), (1, 0),
+        (1, 1), (2, 0),
+        (2, 1),
+    ]);
+    it::assert_equal((0..0).diagonal_product(0..3), vec![]);
+    it::assert_equal((0..3).diagonal_product(0..0), vec![]);
+    it::assert_equal((0..1).diagonal_product(0..3), vec![(0, 0), (0, 1), (0, 2)]);
+
+    // Both sides infinite: every pair on a diagonal comes before the next one.
+    let pairs = (0..).diagonal_product(0..).take(10);
+    it::assert_equal(pairs, vec![
+        (0, 0),
+        (0, 1), (1, 0),
+        (0, 2), (1, 1), (2, 0),
+        (0, 3), (1, 2), (2, 1), (3, 0),
+    ]);
+    let mut it = (0..2).diagonal_product(0..);
+    assert!(it.any(|pair| pair == (1, 100)));
+
+    let mut pairs = (0..4).diagonal_product(0..5).collect_vec();
+    pairs.sort();
+    it::assert_equal(pairs, (0..4).cartesian_product(0..5));
+}
+
+#[test]
+fn multi_diagonal_product() {
+    it::assert_equal((0..3).map(|_| 0..2).multi_diagonal_product(), vec![
+        vec![0, 0, 0],
+        vec![0, 0, 1], vec![0, 1, 0], vec![1, 0, 0],
+        vec![0, 1, 1], vec![1, 0, 1], vec![1, 1, 0],
+        vec![1, 1, 1],
+    ]);
+    it::assert_equal((0..0).map(|_| 0..2).multi_diagonal_product(), <Vec<Vec<_>>>::new());
+    it::assert_equal(vec![0..3].into_iter().multi_diagonal_product(), vec![vec![0], vec![1], vec![2]]);
+    it::assert_equal(vec![0..3, 0..0].into_iter().multi_diagonal_product(), <Vec<Vec<_>>>::new());
+
+    let mut it = vec![0..1, 0..usize::MAX, 0..2].into_iter().multi_diagonal_product();
+    assert!(it.any(|tuple| tuple == vec![0, 50, 1]));
+
+    let sizes = [3, 1, 4, 2];
+    let mut tuples = sizes.iter().map(|&n| 0..n).multi_diagonal_product().collect_vec();
+    assert_eq!(tuples.len(), 24);
+    assert!(tuples.windows(2).all(|w| w[0].iter().sum::<i32>() <= w[1].iter().sum::<i32>()));
+    tuples.sort();
+    it::assert_equal(tuples, sizes.iter().map(|&n| 0..n).multi_cartesian_product());
+}
+
 #[test]
 fn diff_mismatch() {
     let a = vec![1, 2, 3, 4];
@@ -968,6 +1620,132 @@ fn minmax() {
     assert_eq!(max, &Val(0, 2));
 }
 
+#[test]
+fn counts_most_common() {
+    use crate::it::{BTreeMapKind, Counts, OrderedCounts};
+
+    let mut counts: OrderedCounts<char> = "hello world".chars().filter(|c| *c != ' ').collect();
+    assert_eq!(counts.most_common(3), vec![(&'l', 3), (&'o', 2), (&'d', 1)]);
+    assert_eq!(counts.least_common(2), vec![(&'d', 1), (&'e', 1)]);
+    assert_eq!(counts.most_common(0), vec![]);
+    assert_eq!(counts.most_common(100).len(), 7);
+    assert_eq!((counts.total(), counts.len()), (10, 7));
+
+    counts.push_n('z', 4);
+    counts.merge("lol".chars().into_counts());
+    assert_eq!(counts.most_common(2), vec![(&'l', 5), (&'z', 4)]);
+    assert_eq!(counts.get(&'o'), 3);
+    assert_eq!(counts.total(), 17);
+
+    let empty = Counts::<u8, _>::new_in(BTreeMapKind);
+    assert!(empty.is_empty());
+    assert_eq!(empty.least_common(1), vec![]);
+}
+
+#[test]
+fn ordered_map() {
+    use std::hash::{BuildHasherDefault, Hasher};
+    use crate::it::OrderedMap;
+
+    // Every key has the same hash
+    #[derive(Default)]
+    struct ConstHasher;
+
+    impl Hasher for ConstHasher {
+        fn finish(&self) -> u64 { 0 }
+        fn write(&mut self, _: &[u8]) {}
+    }
+
+    let mut map = OrderedMap::with_hasher(BuildHasherDefault::<ConstHasher>::default());
+    for (key, value) in "ordered".chars().zip(0..) {
+        map.insert(key, value);
+    }
+    itertools::assert_equal(map.iter(), vec![(&'o', &0), (&'r', &4), (&'d', &6), (&'e', &5)]);
+    assert_eq!(map.remove(&'r'), Some(4));
+    assert_eq!(map.remove(&'r'), None);
+    assert_eq!((map[&'o'], map[&'d'], map[&'e']), (0, 6, 5));
+    *map.get_mut(&'o').unwrap() += 10;
+    map.insert('r', 7);
+    itertools::assert_equal(map.keys(), &['o', 'd', 'e', 'r']);
+    itertools::assert_equal(map.values(), &[10, 6, 5, 7]);
+    assert_eq!(map.get_index(1), Some((&'d', &6)));
+    assert_eq!(map.len(), 4);
+
+    let map: OrderedMap<_, _> = map.into_iter().rev().collect();
+    assert_eq!(format!("{:?}", map), "{'r': 7, 'e': 5, 'd': 6, 'o': 10}");
+}
+
+#[test]
+fn aggregate_many_owned_values() {
+    use crate::it::aggregations::{First, Last, Max, Min};
+
+    let words = vec!["pear", "fig", "apple", "kiwi", "plum", "banana"];
+    let lookup = words.into_iter()
+        .map(String::from)
+        .into_grouping_map_by(|word| word.len() % 2)
+        .aggregate_many((First, Last, Min, Max));
+    assert_eq!(lookup[&0], ("pear".to_string(), "banana".to_string(),
+                            "banana".to_string(), "plum".to_string()));
+    assert_eq!(lookup[&1], ("fig".to_string(), "apple".to_string(),
+                            "apple".to_string(), "fig".to_string()));
+}
+
+#[test]
+fn ordered_group_maps() {
+    use crate::it::{HashMapKind, OrderedMap};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::BuildHasherDefault;
+
+    let data = vec![(2, 'a'), (0, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+    let lookup = data.iter().cloned().into_ordered_group_map();
+    itertools::assert_equal(lookup, vec![(2, vec!['a', 'c']), (0, vec!['b', 'e']), (1, vec!['d'])]);
+
+    // Discarded groups go last when they come back, the others keep their place
+    let lookup = data.iter().cloned()
+        .into_grouping_map()
+        .ordered()
+        .aggregate(|acc: Option<String>, &key, val| {
+            if key == 2 && val == 'c' {
+                None
+            } else {
+                Some(acc.unwrap_or_default() + &val.to_string())
+            }
+        });
+    itertools::assert_equal(lookup, vec![(0, "be".to_string()), (1, "d".to_string())]);
+
+    let lookup = vec![4, 1, 2, 6, 5, 3].into_iter()
+        .into_grouping_map_by(|&n| n % 2)
+        .ordered()
+        .then_by(|&n| n > 3)
+        .collect::<Vec<_>>();
+    itertools::assert_equal(lookup.keys(), &[0, 1]);
+    itertools::assert_equal(lookup[&0].iter(), vec![(&true, &vec![4, 6]), (&false, &vec![2])]);
+    itertools::assert_equal(lookup[&1].iter(), vec![(&false, &vec![1, 3]), (&true, &vec![5])]);
+
+    // Ordering after `then_by` keeps both levels of maps, and their hasher
+    type Hasher = BuildHasherDefault<DefaultHasher>;
+    let lookup: OrderedMap<_, OrderedMap<_, _, Hasher>, Hasher> = vec![4, 1, 2, 6, 5, 3].into_iter()
+        .into_grouping_map_by_in(HashMapKind::with_hasher(Hasher::default()), |&n| n % 2)
+        .then_by(|&n| n > 3)
+        .ordered()
+        .collect::<Vec<_>>();
+    itertools::assert_equal(lookup.keys(), &[0, 1]);
+    itertools::assert_equal(lookup[&0].iter(), vec![(&true, &vec![4, 6]), (&false, &vec![2])]);
+    itertools::assert_equal(lookup[&1].iter(), vec![(&false, &vec![1, 3]), (&true, &vec![5])]);
+
+    // Inner keys updated again after later ones keep their place
+    let sales = vec![("north", "apple", 3), ("north", "pear", 2), ("north", "apple", 4),
+                     ("south", "plum", 1), ("north", "fig", 5), ("north", "pear", 1)];
+    let lookup = sales.into_iter()
+        .into_grouping_map_by(|&(region, _, _)| region)
+        .then_by(|&(_, product, _)| product)
+        .ordered()
+        .fold(0, |acc, _key, (_, _, n)| acc + n);
+    itertools::assert_equal(lookup.keys(), &["north", "south"]);
+    itertools::assert_equal(lookup[&"north"].iter(), vec![(&"apple", &7), (&"pear", &3), (&"fig", &5)]);
+    itertools::assert_equal(lookup[&"south"].iter(), vec![(&"plum", &1)]);
+}
+
 #[test]
 fn format() {
     let data = [0, 1, 2, 3];